@@ -8,25 +8,52 @@
 //! measure of redundancy, some are listed below:
 //!
 //! * The traits only guarantee a stable location while no `&mut self` methods are called upon
-//! `Self` (with the exception of [`write_buffer`](trait.WriteBuffer.html#tymethod.write_buffer) in
-//! our case). This is to allow types like `Vec`, this restriction doesn't apply to `Self::Target`.
+//!   `Self` (with the exception of [`write_buffer`](trait.WriteBuffer.html#tymethod.write_buffer) in
+//!   our case). This is to allow types like `Vec`, this restriction doesn't apply to `Self::Target`.
 //!
 //! * [`ReadBuffer`] and [`WriteBuffer`] guarantee a stable location for as long as the DMA transfer
-//! occurs. Given the intrinsics of `mem::forget` and the Rust language itself, a
-//! 'static lifetime is usually required.
+//!   occurs. Given the intrinsics of `mem::forget` and the Rust language itself, a
+//!   'static lifetime is usually required.
+//!
+//! * Empty buffers report a length of zero, but their pointer may be dangling. It is still non-null
+//!   and aligned for the word type, as is the case for empty slices and `Box<[T]>`. DMA engines
+//!   that can't handle this should check the length before starting a transfer.
 //!
 //! The above list is not exhaustive, for a complete set of requirements and guarantees, the
 //! documentation of each trait and method should be analyzed.
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::{
+    cell::{Cell, UnsafeCell},
+    convert::TryFrom,
+    marker::PhantomData,
     mem::{self, MaybeUninit},
+    num::Wrapping,
     ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
 };
 use stable_deref_trait::StableDeref;
 
 /// Trait for buffers that can be given to DMA for reading.
 ///
+/// A reference `&B` to a buffer is not a buffer itself, unless it is `'static` and `B` is a
+/// [`ReadTarget`]. If a transfer borrowing `B` was forgotten, `B` could be dropped while DMA is
+/// still reading it:
+///
+/// ```compile_fail
+/// use embedded_dma::ReadBuffer;
+///
+/// fn send<B: ReadBuffer>(_buffer: B) {}
+///
+/// let buffer = [0u8; 32];
+/// send(&buffer);
+/// ```
+///
+/// Generic code holding a `&B` can call the trait methods through the reference instead.
+///
 /// # Safety
 ///
 /// The implementing type must be safe to use for DMA reads. This means:
@@ -37,9 +64,22 @@ use stable_deref_trait::StableDeref;
 ///     times.
 ///   - The memory specified by the pointer and size returned by `read_buffer`
 ///     must not be freed during the transfer it is used in as long as `self` is not dropped.
+/// - `WORD_SIZE` must be `mem::size_of::<Self::Word>()`, i.e. it must not be overridden with a
+///   different value.
+/// - `LEN` must only be `Some(n)` if `read_buffer` returns a size of `n` words for every buffer
+///   of the type.
 pub unsafe trait ReadBuffer {
     type Word;
 
+    /// Size of a single word in bytes.
+    const WORD_SIZE: usize = mem::size_of::<Self::Word>();
+
+    /// Buffer size in words, if it is the same for every buffer of this type.
+    ///
+    /// This is `Some` for buffers backed by arrays, and usable in const contexts, e.g. to check
+    /// the size of a descriptor table at compile time.
+    const LEN: Option<usize> = None;
+
     /// Provide a buffer usable for DMA reads.
     ///
     /// The return value is:
@@ -52,10 +92,279 @@ pub unsafe trait ReadBuffer {
     /// Once this method has been called, it is unsafe to call any `&mut self`
     /// methods on this object as long as the returned value is in use (by DMA).
     unsafe fn read_buffer(&self) -> (*const Self::Word, usize);
+
+    /// Provide a buffer usable for DMA reads, as a `NonNull` pointer.
+    ///
+    /// This is equivalent to [`read_buffer`](ReadBuffer::read_buffer), buffers that satisfy the
+    /// requirements of this trait are never null.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_nonnull(&self) -> (NonNull<Self::Word>, usize) {
+        let (ptr, len) = self.read_buffer();
+        (NonNull::new_unchecked(ptr as *mut Self::Word), len)
+    }
+
+    /// Provide a buffer usable for DMA reads, as a slice pointer.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_slice(&self) -> *const [Self::Word] {
+        let (ptr, len) = self.read_buffer();
+        ptr::slice_from_raw_parts(ptr, len)
+    }
+
+    /// Provide a buffer usable for DMA reads, as start and end pointers.
+    ///
+    /// The end pointer is one past the last word of the buffer, i.e. `start + len` words.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_range(&self) -> (*const Self::Word, *const Self::Word) {
+        let (ptr, len) = self.read_buffer();
+        (ptr, ptr.add(len))
+    }
+
+    /// Provide a buffer usable for byte-wise DMA reads.
+    ///
+    /// The return value is:
+    ///
+    /// - pointer to the start of the buffer
+    /// - buffer size in bytes
+    ///
+    /// Overflow of the byte size is caught by a debug assertion, in release builds the size
+    /// saturates at `usize::MAX`.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_bytes(&self) -> (*const u8, usize) {
+        let (ptr, len) = self.read_buffer();
+        let Bytes(bytes) = Words(len).to_bytes::<Self::Word>();
+        (ptr as *const u8, bytes)
+    }
+
+    /// Provide a buffer usable for byte-wise DMA reads, e.g. by byte-oriented peripherals.
+    ///
+    /// The same as [`read_buffer_bytes`](ReadBuffer::read_buffer_bytes).
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_as_bytes(&self) -> (*const u8, usize) {
+        self.read_buffer_bytes()
+    }
+
+    /// Provide a buffer usable for DMA reads, with the size as [`Words`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_words(&self) -> (*const Self::Word, Words) {
+        let (ptr, len) = self.read_buffer();
+        (ptr, Words(len))
+    }
+
+    /// Describe the buffer provided for DMA reads, e.g. for logging.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_info(&self) -> BufferInfo {
+        let (ptr, len) = self.read_buffer();
+        BufferInfo::new::<Self::Word>(ptr as usize, len)
+    }
+
+    /// Provide a buffer usable for DMA reads, checking that its size in bytes doesn't exceed
+    /// `isize::MAX`.
+    ///
+    /// Rust doesn't allow larger allocations, so a larger buffer indicates a broken
+    /// implementation. Returns `None` in that case.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_checked(&self) -> Option<(*const Self::Word, usize)> {
+        let (ptr, len) = self.read_buffer();
+        match len.checked_mul(mem::size_of::<Self::Word>()) {
+            Some(bytes) if bytes <= isize::MAX as usize => Some((ptr, len)),
+            _ => None,
+        }
+    }
+
+    /// Provide a buffer usable for DMA reads, checking that it is aligned to `align` bytes.
+    ///
+    /// As with `core::alloc::Layout`, `align` must be a non-zero power of two. Other values are
+    /// rejected with an `UnalignedError`, as no buffer can be aligned to them.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_aligned(
+        &self,
+        align: usize,
+    ) -> Result<(*const Self::Word, usize), UnalignedError> {
+        let (ptr, len) = self.read_buffer();
+        UnalignedError::check(ptr as usize, align)?;
+        Ok((ptr, len))
+    }
+
+    /// Provide a buffer usable for DMA reads, checking that it lies within `region`.
+    ///
+    /// This lets HALs reject buffers in memory the DMA can't access, e.g. core-coupled RAM.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_in_region(
+        &self,
+        region: &MemoryRegion,
+    ) -> Result<(*const Self::Word, usize), OutOfRegionError> {
+        let (ptr, len) = self.read_buffer();
+        region.check(ptr as usize, Words(len).to_bytes::<Self::Word>())?;
+        Ok((ptr, len))
+    }
+
+    /// Provide a buffer usable for DMA reads, checking that it holds at least `min` words.
+    ///
+    /// This is useful for peripherals that need a minimum transfer size, e.g. a FIFO threshold.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    #[doc(alias = "require_min_len")]
+    unsafe fn read_buffer_min_len(
+        &self,
+        min: usize,
+    ) -> Result<(*const Self::Word, usize), TooShortError> {
+        match self.read_buffer() {
+            (ptr, len) if len >= min => Ok((ptr, len)),
+            (_, len) => Err(TooShortError { words: len, min }),
+        }
+    }
+
+    /// Provide a buffer usable for DMA reads, with the size checked to fit a 16 bit count
+    /// register.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_u16(&self) -> Result<(*const Self::Word, u16), TooLargeError> {
+        let (ptr, len) = self.read_buffer();
+        u16::try_from(len)
+            .map(|len| (ptr, len))
+            .map_err(|_| TooLargeError::new(len, u16::MAX.into()))
+    }
+
+    /// Provide a buffer usable for DMA reads, with the size checked to fit a 32 bit count
+    /// register.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_u32(&self) -> Result<(*const Self::Word, u32), TooLargeError> {
+        let (ptr, len) = self.read_buffer();
+        u32::try_from(len)
+            .map(|len| (ptr, len))
+            .map_err(|_| TooLargeError::new(len, u32::MAX as usize))
+    }
+
+    /// Check whether the buffer is empty, e.g. to avoid programming a zero-length transfer.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_buffer_is_empty(&self) -> bool {
+        self.read_buffer().1 == 0
+    }
+
+    /// Split off the first word of the buffer, e.g. a command word delivered through a register.
+    ///
+    /// Returns a pointer to the first word, and the pointer and length of the remaining buffer.
+    /// Returns `None` if the buffer is empty.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    #[allow(clippy::type_complexity)]
+    unsafe fn read_buffer_split_first(
+        &self,
+    ) -> Option<(*const Self::Word, (*const Self::Word, usize))> {
+        match self.read_buffer() {
+            (_, 0) => None,
+            (ptr, len) => Some((ptr, (ptr.add(1), len - 1))),
+        }
+    }
+
+    /// Split off the last word of the buffer, e.g. a trailing CRC.
+    ///
+    /// Returns a pointer to the last word, and the pointer and length of the remaining buffer.
+    /// Returns `None` if the buffer is empty.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    #[allow(clippy::type_complexity)]
+    unsafe fn read_buffer_split_last(
+        &self,
+    ) -> Option<(*const Self::Word, (*const Self::Word, usize))> {
+        match self.read_buffer() {
+            (_, 0) => None,
+            (ptr, len) => Some((ptr.add(len - 1), (ptr, len - 1))),
+        }
+    }
+
+    /// Iterate over the buffer in chunks of at most `max` words.
+    ///
+    /// Each item is a pointer to the start of the chunk and the chunk size in words. The chunks
+    /// are contiguous and cover the whole buffer, only the last chunk may be shorter than `max`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn read_chunks(&self, max: usize) -> ReadChunks<'_, Self> {
+        assert!(max != 0, "chunk size must be non-zero");
+        let (ptr, len) = self.read_buffer();
+        ReadChunks {
+            ptr,
+            len,
+            max,
+            _buffer: PhantomData,
+        }
+    }
+
+    /// Iterate over the buffer in chunks of `M` words, with `M` known at compile time.
+    ///
+    /// This is the same as [`read_chunks`](ReadBuffer::read_chunks) with a `max` of `M`. If the
+    /// buffer length isn't a multiple of `M`, the last chunk holds the remaining words.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `M` is zero.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`read_buffer`](ReadBuffer::read_buffer).
+    unsafe fn split_array<const M: usize>(&self) -> ReadChunks<'_, Self> {
+        self.read_chunks(M)
+    }
 }
 
 /// Trait for buffers that can be given to DMA for writing.
 ///
+/// As with [`ReadBuffer`], a reference `&mut B` to a buffer is not a buffer itself, unless it is
+/// `'static` and `B` is a [`WriteTarget`]. `&mut B` is `StableDeref`, so a forwarding impl would
+/// conflict with the blanket impl, and a short-lived `&mut B` could be dropped while DMA is still
+/// writing `B`. Generic code holding a `&mut B` can call the trait methods through the reference
+/// instead.
+///
 /// # Safety
 ///
 /// The implementing type must be safe to use for DMA writes. This means:
@@ -68,9 +377,22 @@ pub unsafe trait ReadBuffer {
 ///     times.
 ///   - The memory specified by the pointer and size returned by `write_buffer`
 ///     must not be freed during the transfer as long as `self` is not dropped.
+/// - `WORD_SIZE` must be `mem::size_of::<Self::Word>()`, i.e. it must not be overridden with a
+///   different value.
+/// - `LEN` must only be `Some(n)` if `write_buffer` returns a size of `n` words for every buffer
+///   of the type.
 pub unsafe trait WriteBuffer {
     type Word;
 
+    /// Size of a single word in bytes.
+    const WORD_SIZE: usize = mem::size_of::<Self::Word>();
+
+    /// Buffer size in words, if it is the same for every buffer of this type.
+    ///
+    /// This is `Some` for buffers backed by arrays, and usable in const contexts, e.g. to check
+    /// the size of a descriptor table at compile time.
+    const LEN: Option<usize> = None;
+
     /// Provide a buffer usable for DMA writes.
     ///
     /// The return value is:
@@ -84,154 +406,5531 @@ pub unsafe trait WriteBuffer {
     /// methods, except for `write_buffer`, on this object as long as the
     /// returned value is in use (by DMA).
     unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize);
-}
 
-// Blanket implementations for common DMA buffer types.
+    /// Provide the full capacity of the buffer for DMA writes.
+    ///
+    /// For growable containers, such as a `heapless::Vec` in a [`Growable`], the buffer provided
+    /// by [`write_buffer`](WriteBuffer::write_buffer) only covers the current length, while this
+    /// covers the maximum the container can hold. For other buffers, both are the same.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.write_buffer()
+    }
 
-unsafe impl<B, T> ReadBuffer for B
-where
-    B: Deref<Target = T> + StableDeref + 'static,
-    T: ReadTarget + ?Sized,
-{
-    type Word = T::Word;
+    /// Provide a buffer usable for DMA writes, as a `NonNull` pointer.
+    ///
+    /// This is equivalent to [`write_buffer`](WriteBuffer::write_buffer), buffers that satisfy
+    /// the requirements of this trait are never null.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_nonnull(&mut self) -> (NonNull<Self::Word>, usize) {
+        let (ptr, len) = self.write_buffer();
+        (NonNull::new_unchecked(ptr), len)
+    }
 
-    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
-        self.as_read_buffer()
+    /// Provide a buffer usable for DMA writes, as a slice pointer.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_slice(&mut self) -> *mut [Self::Word] {
+        let (ptr, len) = self.write_buffer();
+        ptr::slice_from_raw_parts_mut(ptr, len)
     }
-}
 
-unsafe impl<B, T> WriteBuffer for B
-where
-    B: DerefMut<Target = T> + StableDeref + 'static,
-    T: WriteTarget + ?Sized,
-{
-    type Word = T::Word;
+    /// Provide a buffer usable for DMA writes, as start and end pointers.
+    ///
+    /// The end pointer is one past the last word of the buffer, i.e. `start + len` words.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_range(&mut self) -> (*mut Self::Word, *mut Self::Word) {
+        let (ptr, len) = self.write_buffer();
+        (ptr, ptr.add(len))
+    }
 
-    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
-        self.as_write_buffer()
+    /// Provide a buffer usable for byte-wise DMA writes.
+    ///
+    /// The return value is:
+    ///
+    /// - pointer to the start of the buffer
+    /// - buffer size in bytes
+    ///
+    /// Overflow of the byte size is caught by a debug assertion, in release builds the size
+    /// saturates at `usize::MAX`.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_bytes(&mut self) -> (*mut u8, usize) {
+        let (ptr, len) = self.write_buffer();
+        let Bytes(bytes) = Words(len).to_bytes::<Self::Word>();
+        (ptr as *mut u8, bytes)
     }
-}
 
-/// Trait for DMA word types used by the blanket DMA buffer impls.
-///
-/// # Safety
-///
-/// Types that implement this trait must be valid for every possible byte
-/// pattern. This is to ensure that, whatever DMA writes into the buffer,
-/// we won't get UB due to invalid values.
-pub unsafe trait Word {}
+    /// Provide a buffer usable for byte-wise DMA writes, e.g. by byte-oriented peripherals.
+    ///
+    /// The same as [`write_buffer_bytes`](WriteBuffer::write_buffer_bytes).
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_as_bytes(&mut self) -> (*mut u8, usize) {
+        self.write_buffer_bytes()
+    }
 
-unsafe impl Word for u8 {}
-unsafe impl Word for i8 {}
-unsafe impl Word for u16 {}
-unsafe impl Word for i16 {}
-unsafe impl Word for u32 {}
-unsafe impl Word for i32 {}
-unsafe impl Word for u64 {}
-unsafe impl Word for i64 {}
+    /// Provide a buffer usable for DMA writes, with the size as [`Words`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_words(&mut self) -> (*mut Self::Word, Words) {
+        let (ptr, len) = self.write_buffer();
+        (ptr, Words(len))
+    }
 
-/// Trait for `Deref` targets used by the blanket `DmaReadBuffer` impl.
-///
-/// This trait exists solely to work around
-/// https://github.com/rust-lang/rust/issues/20400.
-///
-/// # Safety
-///
-/// - `as_read_buffer` must adhere to the safety requirements
-///   documented for [`ReadBuffer::read_buffer`].
-pub unsafe trait ReadTarget {
-    type Word: Word;
+    /// Describe the buffer provided for DMA writes, e.g. for logging.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_info(&mut self) -> BufferInfo {
+        let (ptr, len) = self.write_buffer();
+        BufferInfo::new::<Self::Word>(ptr as usize, len)
+    }
 
-    fn as_read_buffer(&self) -> (*const Self::Word, usize) {
-        let len = mem::size_of_val(self) / mem::size_of::<Self::Word>();
-        let ptr = self as *const _ as *const Self::Word;
-        (ptr, len)
+    /// Provide a buffer usable for DMA writes, checking that it holds at least `min` words.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    #[doc(alias = "require_min_len")]
+    unsafe fn write_buffer_min_len(
+        &mut self,
+        min: usize,
+    ) -> Result<(*mut Self::Word, usize), TooShortError> {
+        match self.write_buffer() {
+            (ptr, len) if len >= min => Ok((ptr, len)),
+            (_, len) => Err(TooShortError { words: len, min }),
+        }
     }
-}
 
-/// Trait for `DerefMut` targets used by the blanket `DmaWriteBuffer` impl.
-///
-/// This trait exists solely to work around
-/// https://github.com/rust-lang/rust/issues/20400.
-///
-/// # Safety
-///
-/// - `as_write_buffer` must adhere to the safety requirements
-///   documented for [`WriteBuffer::write_buffer`].
-pub unsafe trait WriteTarget {
-    type Word: Word;
+    /// Provide a buffer usable for DMA writes, with the size checked to fit a 16 bit count
+    /// register.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_u16(&mut self) -> Result<(*mut Self::Word, u16), TooLargeError> {
+        let (ptr, len) = self.write_buffer();
+        u16::try_from(len)
+            .map(|len| (ptr, len))
+            .map_err(|_| TooLargeError::new(len, u16::MAX.into()))
+    }
 
-    fn as_write_buffer(&mut self) -> (*mut Self::Word, usize) {
-        let len = mem::size_of_val(self) / mem::size_of::<Self::Word>();
-        let ptr = self as *mut _ as *mut Self::Word;
-        (ptr, len)
+    /// Provide a buffer usable for DMA writes, with the size checked to fit a 32 bit count
+    /// register.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_u32(&mut self) -> Result<(*mut Self::Word, u32), TooLargeError> {
+        let (ptr, len) = self.write_buffer();
+        u32::try_from(len)
+            .map(|len| (ptr, len))
+            .map_err(|_| TooLargeError::new(len, u32::MAX as usize))
     }
-}
 
-unsafe impl<W: Word> ReadTarget for W {
-    type Word = W;
-}
+    /// Check whether the buffer is empty, e.g. to avoid programming a zero-length transfer.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_is_empty(&mut self) -> bool {
+        self.write_buffer().1 == 0
+    }
 
-unsafe impl<W: Word> WriteTarget for W {
-    type Word = W;
-}
+    /// Provide a buffer usable for DMA writes, checking that its size in bytes doesn't exceed
+    /// `isize::MAX`.
+    ///
+    /// Rust doesn't allow larger allocations, so a larger buffer indicates a broken
+    /// implementation. Returns `None` in that case.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_checked(&mut self) -> Option<(*mut Self::Word, usize)> {
+        let (ptr, len) = self.write_buffer();
+        match len.checked_mul(mem::size_of::<Self::Word>()) {
+            Some(bytes) if bytes <= isize::MAX as usize => Some((ptr, len)),
+            _ => None,
+        }
+    }
 
-unsafe impl<T: ReadTarget> ReadTarget for [T] {
-    type Word = T::Word;
+    /// Provide a buffer usable for DMA writes, checking that it is aligned to `align` bytes.
+    ///
+    /// As with `core::alloc::Layout`, `align` must be a non-zero power of two. Other values are
+    /// rejected with an `UnalignedError`, as no buffer can be aligned to them.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_aligned(
+        &mut self,
+        align: usize,
+    ) -> Result<(*mut Self::Word, usize), UnalignedError> {
+        let (ptr, len) = self.write_buffer();
+        UnalignedError::check(ptr as usize, align)?;
+        Ok((ptr, len))
+    }
+
+    /// Provide a buffer usable for DMA writes, checking that it lies within `region`.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`write_buffer`](WriteBuffer::write_buffer).
+    unsafe fn write_buffer_in_region(
+        &mut self,
+        region: &MemoryRegion,
+    ) -> Result<(*mut Self::Word, usize), OutOfRegionError> {
+        let (ptr, len) = self.write_buffer();
+        region.check(ptr as usize, Words(len).to_bytes::<Self::Word>())?;
+        Ok((ptr, len))
+    }
 }
 
-unsafe impl<T: WriteTarget> WriteTarget for [T] {
-    type Word = T::Word;
+/// Trait for buffers that can be given to DMA for both reading and writing.
+///
+/// # Safety
+///
+/// The implementing type must be safe to use for DMA reads and writes. This means:
+///
+/// - It must be a pointer that references the actual buffer.
+/// - `Target` must be a type that is valid for any possible byte pattern.
+/// - As long as no `&mut self` method, except for `read_write_buffer`, is called on
+///   the implementing object:
+///   - `read_write_buffer` must always return the same value, if called multiple
+///     times.
+///   - The read and write pointers returned by `read_write_buffer` must reference the same
+///     memory.
+///   - The memory specified by the pointers and size returned by `read_write_buffer`
+///     must not be freed during the transfer as long as `self` is not dropped.
+pub unsafe trait ReadWriteBuffer {
+    type Word;
+
+    /// Provide a buffer usable for DMA reads and writes.
+    ///
+    /// The return value is:
+    ///
+    /// - pointer to the start of the buffer, for reading
+    /// - pointer to the start of the buffer, for writing
+    /// - buffer size in words
+    ///
+    /// # Safety
+    ///
+    /// Once this method has been called, it is unsafe to call any `&mut self`
+    /// methods, except for `read_write_buffer`, on this object as long as the
+    /// returned value is in use (by DMA).
+    unsafe fn read_write_buffer(&mut self) -> (*const Self::Word, *mut Self::Word, usize);
 }
 
-unsafe impl<T: ReadTarget, const N: usize> ReadTarget for [T; N] {
+/// A size in words of a DMA buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Words(pub usize);
+
+/// A size in bytes of a DMA buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Bytes(pub usize);
+
+impl Words {
+    /// Convert the size to bytes, for words of type `W`.
+    ///
+    /// Overflow is caught by a debug assertion, in release builds the size saturates at
+    /// `usize::MAX`.
+    pub fn to_bytes<W>(self) -> Bytes {
+        let bytes = self.0.checked_mul(mem::size_of::<W>());
+        debug_assert!(bytes.is_some(), "DMA buffer size in bytes overflows usize");
+        Bytes(bytes.unwrap_or(usize::MAX))
+    }
+}
+
+/// Description of a DMA buffer, e.g. for logging or tracing transfers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferInfo {
+    /// Address of the start of the buffer.
+    pub addr: usize,
+    /// Buffer size in words.
+    pub words: usize,
+    /// Size of a single word in bytes.
+    pub word_size: usize,
+    /// Buffer size in bytes.
+    pub bytes: usize,
+}
+
+impl BufferInfo {
+    fn new<W>(addr: usize, words: usize) -> Self {
+        let Bytes(bytes) = Words(words).to_bytes::<W>();
+        BufferInfo {
+            addr,
+            words,
+            word_size: mem::size_of::<W>(),
+            bytes,
+        }
+    }
+}
+
+/// Error returned when a buffer has more words than a DMA transfer count register can hold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TooLargeError {
+    /// Size of the buffer in words.
+    pub words: usize,
+    /// Maximum number of words supported.
+    pub max: usize,
+}
+
+impl TooLargeError {
+    fn new(words: usize, max: usize) -> Self {
+        TooLargeError { words, max }
+    }
+}
+
+/// Error returned when a buffer has fewer words than a DMA transfer requires.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TooShortError {
+    /// Size of the buffer in words.
+    pub words: usize,
+    /// Minimum number of words required.
+    pub min: usize,
+}
+
+/// Iterator over chunks of a [`ReadBuffer`], created by [`ReadBuffer::read_chunks`].
+pub struct ReadChunks<'a, B: ReadBuffer + ?Sized> {
+    ptr: *const B::Word,
+    len: usize,
+    max: usize,
+    _buffer: PhantomData<&'a B>,
+}
+
+impl<'a, B: ReadBuffer + ?Sized> Iterator for ReadChunks<'a, B> {
+    type Item = (*const B::Word, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let chunk = (self.ptr, self.len.min(self.max));
+        self.ptr = self.ptr.wrapping_add(chunk.1);
+        self.len -= chunk.1;
+        Some(chunk)
+    }
+}
+
+/// Trait for the peripheral side of a DMA transfer.
+///
+/// # Safety
+///
+/// The implementing type must describe a location that is safe to access by DMA. This means:
+///
+/// - `address` must be the address of a peripheral register or memory region that can be read
+///   or written by DMA in units of `Word`.
+/// - `address` and `increment` must always return the same value, if called multiple times.
+pub unsafe trait PeripheralAddress {
+    type Word: Word;
+
+    /// Address of the peripheral register.
+    fn address(&self) -> usize;
+
+    /// Whether DMA should increment the peripheral address after each word.
+    fn increment(&self) -> bool {
+        false
+    }
+}
+
+/// A fixed peripheral register, usable as a [`PeripheralAddress`].
+#[derive(Clone, Copy, Debug)]
+pub struct PeripheralRegister<W> {
+    address: usize,
+    _word: PhantomData<W>,
+}
+
+impl<W> PeripheralRegister<W> {
+    /// Describe the peripheral register at `address`.
+    ///
+    /// # Safety
+    ///
+    /// `address` must be a register that can be accessed by DMA in units of `W`.
+    pub const unsafe fn new(address: usize) -> Self {
+        PeripheralRegister {
+            address,
+            _word: PhantomData,
+        }
+    }
+}
+
+unsafe impl<W: Word> PeripheralAddress for PeripheralRegister<W> {
+    type Word = W;
+
+    fn address(&self) -> usize {
+        self.address
+    }
+}
+
+/// Error returned when a buffer doesn't have the required alignment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnalignedError {
+    /// Address of the buffer.
+    pub ptr: usize,
+    /// Required alignment in bytes.
+    pub required: usize,
+}
+
+impl UnalignedError {
+    fn check(ptr: usize, align: usize) -> Result<(), Self> {
+        if align.is_power_of_two() && ptr % align == 0 {
+            Ok(())
+        } else {
+            Err(UnalignedError {
+                ptr,
+                required: align,
+            })
+        }
+    }
+}
+
+/// A range of addresses accessible by DMA, from `start` up to, but not including, `end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryRegion {
+    /// First address of the region.
+    pub start: usize,
+    /// Address one past the end of the region.
+    pub end: usize,
+}
+
+impl MemoryRegion {
+    /// Create a region from `start` up to, but not including, `end`.
+    pub const fn new(start: usize, end: usize) -> Self {
+        MemoryRegion { start, end }
+    }
+
+    /// Check whether `bytes` bytes starting at `addr` lie within the region.
+    pub fn contains(&self, addr: usize, bytes: usize) -> bool {
+        match addr.checked_add(bytes) {
+            Some(end) => addr >= self.start && end <= self.end,
+            None => false,
+        }
+    }
+
+    fn check(&self, addr: usize, bytes: Bytes) -> Result<(), OutOfRegionError> {
+        if self.contains(addr, bytes.0) {
+            Ok(())
+        } else {
+            Err(OutOfRegionError {
+                addr,
+                bytes: bytes.0,
+            })
+        }
+    }
+}
+
+/// Error returned when a buffer doesn't lie within the required [`MemoryRegion`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfRegionError {
+    /// Address of the buffer.
+    pub addr: usize,
+    /// Size of the buffer in bytes.
+    pub bytes: usize,
+}
+
+// Blanket implementations for common DMA buffer types.
+
+/// With the `alloc` feature, this includes shared pointers such as `Rc<[T]>` and `Arc<[T]>`. They
+/// are only usable for DMA reads, as they don't give out mutable access to their contents.
+///
+/// `heapless::pool` boxes are covered as well. As with other owning pointers, the box is
+/// owned by the transfer, so the memory only returns to the pool once the transfer is dropped.
+///
+/// `Pin<P>` isn't covered, as it doesn't implement `StableDeref`. For the usual DMA targets, which
+/// are `Unpin`, the pointer can be used directly after unwrapping it with `Pin::into_inner`.
+///
+/// Arrays aren't buffers by value, even small ones such as command sequences. Moving the array
+/// into a transfer would move its contents too, so the pointer handed to DMA would dangle:
+///
+/// ```compile_fail
+/// use embedded_dma::ReadBuffer;
+///
+/// fn send<B: ReadBuffer>(_buffer: B) {}
+///
+/// send([0x9Fu8, 0, 0, 0]);
+/// ```
+///
+/// A `&'static [W; N]` reference is used instead:
+///
+/// ```
+/// use embedded_dma::ReadBuffer;
+///
+/// fn send<B: ReadBuffer>(_buffer: B) {}
+///
+/// static READ_ID: [u8; 4] = [0x9F, 0, 0, 0];
+/// send(&READ_ID);
+/// ```
+unsafe impl<B, T> ReadBuffer for B
+where
+    B: Deref<Target = T> + StableDeref + 'static,
+    T: ReadTarget + ?Sized,
+{
     type Word = T::Word;
+
+    const LEN: Option<usize> = T::LEN;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        self.as_read_buffer()
+    }
 }
 
-unsafe impl<T: WriteTarget, const N: usize> WriteTarget for [T; N] {
+/// This includes `&'static mut` references, e.g. `&'static mut [u8; 32]`. References to stack
+/// buffers don't live long enough to be used this way:
+///
+/// ```compile_fail
+/// use embedded_dma::WriteBuffer;
+///
+/// fn receive<B: WriteBuffer>(_buffer: B) {}
+///
+/// let mut buffer = [0u8; 32];
+/// receive(&mut buffer);
+/// ```
+unsafe impl<B, T> WriteBuffer for B
+where
+    B: DerefMut<Target = T> + StableDeref + 'static,
+    T: WriteTarget + ?Sized,
+{
     type Word = T::Word;
+
+    const LEN: Option<usize> = T::LEN;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.as_write_buffer()
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.as_write_buffer_capacity()
+    }
 }
 
-unsafe impl<T: WriteTarget> WriteTarget for MaybeUninit<T> {
+unsafe impl<B, T> ReadWriteBuffer for B
+where
+    B: DerefMut<Target = T> + StableDeref + 'static,
+    T: WriteTarget + ?Sized,
+{
     type Word = T::Word;
+
+    unsafe fn read_write_buffer(&mut self) -> (*const Self::Word, *mut Self::Word, usize) {
+        let (ptr, len) = self.as_write_buffer();
+        (ptr, ptr, len)
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use core::any::Any;
+/// Check whether the memory of a DMA source and destination overlaps, e.g. before starting a
+/// memory-to-memory transfer.
+///
+/// The buffers are compared as byte ranges, so their word types may differ. Empty buffers never
+/// overlap.
+pub fn buffers_overlap<R: ReadBuffer, W: WriteBuffer>(r: &R, w: &mut W) -> bool {
+    let (r_ptr, r_len) = unsafe { r.read_buffer_bytes() };
+    let (w_ptr, w_len) = unsafe { w.write_buffer_bytes() };
+    let (r_start, w_start) = (r_ptr as usize, w_ptr as usize);
 
-    fn api_read<W, B>(buffer: B) -> (*const W, usize)
-    where
-        B: ReadBuffer<Word = W>,
-    {
-        unsafe { buffer.read_buffer() }
+    r_len != 0
+        && w_len != 0
+        && r_start < w_start.saturating_add(w_len)
+        && w_start < r_start.saturating_add(r_len)
+}
+
+/// A view into a sub-region of a DMA buffer.
+///
+/// The view covers `len` words, starting `offset` words into the inner buffer.
+#[derive(Clone, Copy)]
+pub struct Sub<B> {
+    buffer: B,
+    offset: usize,
+    len: usize,
+}
+
+impl<B: ReadBuffer> Sub<B> {
+    /// Create a view of `len` words of `buffer`, starting at `offset`.
+    ///
+    /// Returns `None` if the view would exceed the length reported by
+    /// [`ReadBuffer::read_buffer`].
+    pub fn new(buffer: B, offset: usize, len: usize) -> Option<Self> {
+        let (_, inner_len) = unsafe { buffer.read_buffer() };
+        Self::checked(buffer, offset, len, inner_len)
     }
+}
 
-    fn api_write<W, B>(mut buffer: B) -> (*mut W, usize)
-    where
-        B: WriteBuffer<Word = W>,
-    {
-        unsafe { buffer.write_buffer() }
+impl<B: WriteBuffer> Sub<B> {
+    /// Create a view of `len` words of `buffer`, starting at `offset`.
+    ///
+    /// Returns `None` if the view would exceed the length reported by
+    /// [`WriteBuffer::write_buffer`].
+    pub fn new_mut(mut buffer: B, offset: usize, len: usize) -> Option<Self> {
+        let (_, inner_len) = unsafe { buffer.write_buffer() };
+        Self::checked(buffer, offset, len, inner_len)
     }
+}
 
-    #[test]
-    fn read_api() {
-        const SIZE: usize = 128;
-        static BUF: [u8; SIZE] = [0u8; SIZE];
+impl<T: WriteTarget + ?Sized> Sub<&'static mut T> {
+    /// Split the view into two non-overlapping buffers, the first covering the words before `mid`
+    /// and the second covering the rest.
+    ///
+    /// Unlike [`split_at`](Sub::split_at), this doesn't require a `Copy` buffer, so it splits
+    /// `&'static mut` write buffers for double buffering. The reference is consumed, so the
+    /// halves are the only access to the memory afterwards.
+    ///
+    /// Returns `None` if `mid` exceeds the length of the view.
+    #[allow(clippy::type_complexity)]
+    pub fn split_at_mut(mut self, mid: usize) -> Option<(Fixed<T::Word>, Fixed<T::Word>)> {
+        if mid > self.len {
+            return None;
+        }
 
-        let (ptr, size_local) = api_read(&BUF);
-        assert!(unsafe { (&*ptr as &dyn Any).is::<u8>() });
-        assert_eq!(size_local, SIZE);
+        let (ptr, len) = unsafe { self.write_buffer() };
+        unsafe {
+            let first = Fixed::from_raw(ptr, mid);
+            let second = Fixed::from_raw(ptr.add(mid), len - mid);
+            Some((first, second))
+        }
+    }
+}
+
+impl<B> Sub<B> {
+    fn checked(buffer: B, offset: usize, len: usize, inner_len: usize) -> Option<Self> {
+        match offset.checked_add(len) {
+            Some(end) if end <= inner_len => Some(Sub {
+                buffer,
+                offset,
+                len,
+            }),
+            _ => None,
+        }
     }
 
-    #[test]
-    fn write_api() {
-        const SIZE: usize = 128;
-        static mut BUF: [u8; SIZE] = [0u8; SIZE];
+    /// Consume the view, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
 
-        let (ptr, size_local) = api_write(unsafe { &mut BUF });
-        assert!(unsafe { (&*ptr as &dyn Any).is::<u8>() });
-        assert_eq!(size_local, SIZE);
+impl<B: Copy> Sub<B> {
+    /// Split the view into two non-overlapping views, the first covering the words before `mid`
+    /// and the second covering the rest.
+    ///
+    /// This is useful for double buffering, where both halves are handed to DMA in turn. Both
+    /// views reference the same backing buffer, so it must outlive both of them.
+    ///
+    /// Returns `None` if `mid` exceeds the length of the view.
+    pub fn split_at(self, mid: usize) -> Option<(Self, Self)> {
+        if mid > self.len {
+            return None;
+        }
+
+        let first = Sub {
+            buffer: self.buffer,
+            offset: self.offset,
+            len: mid,
+        };
+        let second = Sub {
+            buffer: self.buffer,
+            offset: self.offset + mid,
+            len: self.len - mid,
+        };
+        Some((first, second))
+    }
+}
+
+unsafe impl<B: ReadBuffer> ReadBuffer for Sub<B> {
+    type Word = B::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        let (ptr, _) = self.buffer.read_buffer();
+        (ptr.add(self.offset), self.len)
+    }
+}
+
+unsafe impl<B: WriteBuffer> WriteBuffer for Sub<B> {
+    type Word = B::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        let (ptr, _) = self.buffer.write_buffer();
+        (ptr.add(self.offset), self.len)
+    }
+}
+
+/// A byte-granular view into a sub-region of a DMA buffer.
+///
+/// The view covers `len` bytes, starting `offset` bytes into the inner buffer, e.g. to skip a
+/// 2 byte header in a buffer of `u32` words. The start of the view may not be aligned for the
+/// word type of the inner buffer, so the view is always made of bytes.
+#[doc(alias = "sub_bytes")]
+pub struct RawByteView<B> {
+    buffer: B,
+    offset: usize,
+    len: usize,
+}
+
+impl<B: ReadBuffer> RawByteView<B> {
+    /// Create a view of `len` bytes of `buffer`, starting at `offset` bytes.
+    ///
+    /// Returns `None` if the view would exceed the length reported by
+    /// [`ReadBuffer::read_buffer_bytes`].
+    pub fn new(buffer: B, offset: usize, len: usize) -> Option<Self> {
+        let (_, inner_len) = unsafe { buffer.read_buffer_bytes() };
+        Self::checked(buffer, offset, len, inner_len)
+    }
+}
+
+impl<B: WriteBuffer> RawByteView<B> {
+    /// Create a view of `len` bytes of `buffer`, starting at `offset` bytes.
+    ///
+    /// Returns `None` if the view would exceed the length reported by
+    /// [`WriteBuffer::write_buffer_bytes`].
+    pub fn new_mut(mut buffer: B, offset: usize, len: usize) -> Option<Self> {
+        let (_, inner_len) = unsafe { buffer.write_buffer_bytes() };
+        Self::checked(buffer, offset, len, inner_len)
+    }
+}
+
+impl<B> RawByteView<B> {
+    fn checked(buffer: B, offset: usize, len: usize, inner_len: usize) -> Option<Self> {
+        match offset.checked_add(len) {
+            Some(end) if end <= inner_len => Some(RawByteView {
+                buffer,
+                offset,
+                len,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Consume the view, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+unsafe impl<B: ReadBuffer> ReadBuffer for RawByteView<B> {
+    type Word = u8;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        let (ptr, _) = self.buffer.read_buffer_bytes();
+        (ptr.add(self.offset), self.len)
+    }
+}
+
+unsafe impl<B: WriteBuffer> WriteBuffer for RawByteView<B> {
+    type Word = u8;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        let (ptr, _) = self.buffer.write_buffer_bytes();
+        (ptr.add(self.offset), self.len)
+    }
+}
+
+/// The largest sub-region of a DMA buffer starting at a given alignment.
+///
+/// Unlike [`Aligned`], this doesn't require aligning the buffer itself. Instead, the start of the
+/// buffer is advanced until it is aligned, reducing the length accordingly.
+pub struct AutoAligned<B> {
+    buffer: B,
+    offset: usize,
+    len: usize,
+}
+
+impl<B: ReadBuffer> AutoAligned<B> {
+    /// Carve the region of `buffer` starting at the first address aligned to `align` bytes.
+    ///
+    /// Returns `None` if `align` is not a power of two, or if no word of the buffer reported by
+    /// [`ReadBuffer::read_buffer`] is aligned to `align`.
+    pub fn new(buffer: B, align: usize) -> Option<Self> {
+        let (ptr, len) = unsafe { buffer.read_buffer() };
+        Self::checked(buffer, ptr, len, align)
+    }
+}
+
+impl<B: WriteBuffer> AutoAligned<B> {
+    /// Carve the region of `buffer` starting at the first address aligned to `align` bytes.
+    ///
+    /// Returns `None` if `align` is not a power of two, or if no word of the buffer reported by
+    /// [`WriteBuffer::write_buffer`] is aligned to `align`.
+    pub fn new_mut(mut buffer: B, align: usize) -> Option<Self> {
+        let (ptr, len) = unsafe { buffer.write_buffer() };
+        Self::checked(buffer, ptr, len, align)
+    }
+}
+
+impl<B> AutoAligned<B> {
+    fn checked<W>(buffer: B, ptr: *const W, len: usize, align: usize) -> Option<Self> {
+        if !align.is_power_of_two() {
+            return None;
+        }
+
+        match ptr.align_offset(align) {
+            offset if offset < len => Some(AutoAligned {
+                buffer,
+                offset,
+                len: len - offset,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Consume the view, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+unsafe impl<B: ReadBuffer> ReadBuffer for AutoAligned<B> {
+    type Word = B::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        let (ptr, _) = self.buffer.read_buffer();
+        (ptr.add(self.offset), self.len)
+    }
+}
+
+unsafe impl<B: WriteBuffer> WriteBuffer for AutoAligned<B> {
+    type Word = B::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        let (ptr, _) = self.buffer.write_buffer();
+        (ptr.add(self.offset), self.len)
+    }
+}
+
+/// A DMA buffer truncated to its first `used` words.
+///
+/// Unlike [`Sub`], the pointer of the inner buffer is kept unchanged, only the reported length is
+/// reduced. This is useful for fixed size buffers that are only partially filled.
+#[derive(Clone, Copy)]
+pub struct Truncated<B> {
+    buffer: B,
+    used: usize,
+}
+
+impl<B: ReadBuffer> Truncated<B> {
+    /// Truncate `buffer` to its first `used` words.
+    ///
+    /// Returns `None` if `used` exceeds the length reported by [`ReadBuffer::read_buffer`].
+    pub fn new(buffer: B, used: usize) -> Option<Self> {
+        let (_, inner_len) = unsafe { buffer.read_buffer() };
+        Self::checked(buffer, used, inner_len)
+    }
+}
+
+impl<B: WriteBuffer> Truncated<B> {
+    /// Truncate `buffer` to its first `used` words.
+    ///
+    /// Returns `None` if `used` exceeds the length reported by [`WriteBuffer::write_buffer`].
+    pub fn new_mut(mut buffer: B, used: usize) -> Option<Self> {
+        let (_, inner_len) = unsafe { buffer.write_buffer() };
+        Self::checked(buffer, used, inner_len)
+    }
+}
+
+impl<B> Truncated<B> {
+    fn checked(buffer: B, used: usize, inner_len: usize) -> Option<Self> {
+        if used <= inner_len {
+            Some(Truncated { buffer, used })
+        } else {
+            None
+        }
+    }
+
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+unsafe impl<B: ReadBuffer> ReadBuffer for Truncated<B> {
+    type Word = B::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        let (ptr, _) = self.buffer.read_buffer();
+        (ptr, self.used)
+    }
+}
+
+unsafe impl<B: WriteBuffer> WriteBuffer for Truncated<B> {
+    type Word = B::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        let (ptr, _) = self.buffer.write_buffer();
+        (ptr, self.used)
+    }
+}
+
+/// A DMA buffer with its length rounded down to a multiple of the burst size.
+///
+/// DMA bursts require the transfer length to be a multiple of the burst size. The words past the
+/// last full burst are left out of the buffer, and are available separately as the remainder,
+/// e.g. to transfer them without bursts.
+pub struct BurstPadded<B> {
+    buffer: B,
+    len: usize,
+    remainder: usize,
+}
+
+impl<B: ReadBuffer> BurstPadded<B> {
+    /// Round the length of `buffer` down to a multiple of `burst` words.
+    ///
+    /// Returns `None` if `burst` is zero.
+    pub fn new(buffer: B, burst: usize) -> Option<Self> {
+        let (_, inner_len) = unsafe { buffer.read_buffer() };
+        Self::checked(buffer, burst, inner_len)
+    }
+
+    /// Provide the words past the last full burst for DMA reads.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`ReadBuffer::read_buffer`].
+    pub unsafe fn remainder(&self) -> (*const B::Word, usize) {
+        let (ptr, _) = self.buffer.read_buffer();
+        (ptr.add(self.len), self.remainder)
+    }
+}
+
+impl<B: WriteBuffer> BurstPadded<B> {
+    /// Round the length of `buffer` down to a multiple of `burst` words.
+    ///
+    /// Returns `None` if `burst` is zero.
+    pub fn new_mut(mut buffer: B, burst: usize) -> Option<Self> {
+        let (_, inner_len) = unsafe { buffer.write_buffer() };
+        Self::checked(buffer, burst, inner_len)
+    }
+
+    /// Provide the words past the last full burst for DMA writes.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`WriteBuffer::write_buffer`].
+    pub unsafe fn remainder_mut(&mut self) -> (*mut B::Word, usize) {
+        let (ptr, _) = self.buffer.write_buffer();
+        (ptr.add(self.len), self.remainder)
+    }
+}
+
+impl<B> BurstPadded<B> {
+    fn checked(buffer: B, burst: usize, inner_len: usize) -> Option<Self> {
+        let remainder = inner_len.checked_rem(burst)?;
+        Some(BurstPadded {
+            buffer,
+            len: inner_len - remainder,
+            remainder,
+        })
+    }
+
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+unsafe impl<B: ReadBuffer> ReadBuffer for BurstPadded<B> {
+    type Word = B::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        let (ptr, _) = self.buffer.read_buffer();
+        (ptr, self.len)
+    }
+}
+
+unsafe impl<B: WriteBuffer> WriteBuffer for BurstPadded<B> {
+    type Word = B::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        let (ptr, _) = self.buffer.write_buffer();
+        (ptr, self.len)
+    }
+}
+
+/// A DMA buffer holding a frame, with the payload length in the first word.
+///
+/// The whole buffer is forwarded to DMA, the payload declared by the length prefix is available
+/// through [`payload`](Framed::payload).
+pub struct Framed<B> {
+    buffer: B,
+}
+
+impl<B> Framed<B> {
+    /// Use `buffer` for frames.
+    pub fn new(buffer: B) -> Self {
+        Framed { buffer }
+    }
+
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+impl<B: ReadBuffer> Framed<B>
+where
+    B::Word: Copy,
+    usize: TryFrom<B::Word>,
+{
+    /// The payload of the frame, i.e. the words after the length prefix, as many as it declares.
+    ///
+    /// Returns `None` if the buffer is empty, or if the payload would exceed the buffer.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`ReadBuffer::read_buffer`]. In addition, the length prefix must be
+    /// initialized and must not be written by DMA during this call.
+    pub unsafe fn payload(&self) -> Option<(*const B::Word, usize)> {
+        let (ptr, len) = self.buffer.read_buffer();
+        if len == 0 {
+            return None;
+        }
+
+        match usize::try_from(ptr.read()) {
+            Ok(payload) if payload < len => Some((ptr.add(1), payload)),
+            _ => None,
+        }
+    }
+}
+
+unsafe impl<B: ReadBuffer> ReadBuffer for Framed<B> {
+    type Word = B::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        self.buffer.read_buffer()
+    }
+}
+
+unsafe impl<B: WriteBuffer> WriteBuffer for Framed<B> {
+    type Word = B::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer()
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer_capacity()
+    }
+}
+
+/// Geometry of a two-dimensional DMA transfer, in words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StrideInfo {
+    /// Number of words in each line.
+    pub line_len: usize,
+    /// Number of lines.
+    pub line_count: usize,
+    /// Distance between the starts of two consecutive lines.
+    pub stride: usize,
+}
+
+impl StrideInfo {
+    /// Number of words spanned from the start of the first line to the end of the last line.
+    fn span(&self) -> Option<usize> {
+        match self.line_count {
+            0 => Some(0),
+            n => (n - 1)
+                .checked_mul(self.stride)
+                .and_then(|offset| offset.checked_add(self.line_len)),
+        }
+    }
+}
+
+/// A DMA buffer accessed as a two-dimensional region, e.g. a frame for an LCD or camera
+/// interface.
+pub struct StridedBuffer<B> {
+    buffer: B,
+    info: StrideInfo,
+}
+
+impl<B: ReadBuffer> StridedBuffer<B> {
+    /// Create a strided view of `buffer` with the given geometry.
+    ///
+    /// Returns `None` if the last line would exceed the length reported by
+    /// [`ReadBuffer::read_buffer`].
+    pub fn new(buffer: B, line_len: usize, line_count: usize, stride: usize) -> Option<Self> {
+        let (_, inner_len) = unsafe { buffer.read_buffer() };
+        Self::checked(buffer, line_len, line_count, stride, inner_len)
+    }
+
+    /// Provide the buffer for two-dimensional DMA reads.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`ReadBuffer::read_buffer`].
+    pub unsafe fn strided_read_buffer(&self) -> (*const B::Word, StrideInfo) {
+        let (ptr, _) = self.buffer.read_buffer();
+        (ptr, self.info)
+    }
+}
+
+impl<B: WriteBuffer> StridedBuffer<B> {
+    /// Create a strided view of `buffer` with the given geometry.
+    ///
+    /// Returns `None` if the last line would exceed the length reported by
+    /// [`WriteBuffer::write_buffer`].
+    pub fn new_mut(
+        mut buffer: B,
+        line_len: usize,
+        line_count: usize,
+        stride: usize,
+    ) -> Option<Self> {
+        let (_, inner_len) = unsafe { buffer.write_buffer() };
+        Self::checked(buffer, line_len, line_count, stride, inner_len)
+    }
+
+    /// Provide the buffer for two-dimensional DMA writes.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`WriteBuffer::write_buffer`].
+    pub unsafe fn strided_write_buffer(&mut self) -> (*mut B::Word, StrideInfo) {
+        let (ptr, _) = self.buffer.write_buffer();
+        (ptr, self.info)
+    }
+}
+
+impl<B> StridedBuffer<B> {
+    fn checked(
+        buffer: B,
+        line_len: usize,
+        line_count: usize,
+        stride: usize,
+        inner_len: usize,
+    ) -> Option<Self> {
+        let info = StrideInfo {
+            line_len,
+            line_count,
+            stride,
+        };
+        match info.span() {
+            Some(span) if span <= inner_len => Some(StridedBuffer { buffer, info }),
+            _ => None,
+        }
+    }
+
+    /// Consume the view, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+/// A DMA buffer used as a ring, in circular DMA mode.
+///
+/// The pointer and length of the inner buffer are forwarded unchanged, the wrapper adds helpers
+/// to track the DMA position in the ring.
+pub struct Circular<B> {
+    buffer: B,
+    capacity: usize,
+}
+
+impl<B: WriteBuffer> Circular<B> {
+    /// Use `buffer` as a ring.
+    pub fn new(mut buffer: B) -> Self {
+        let (_, capacity) = unsafe { buffer.write_buffer() };
+        Circular { buffer, capacity }
+    }
+
+    /// Length of the ring in words.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Translate the remaining transfer count of the DMA into the position in the ring that will
+    /// be written next.
+    ///
+    /// DMA peripherals count down the words left until the end of the ring, and reload the count
+    /// when it is reached, so a `remaining` count equal to the capacity is position 0.
+    pub fn position_from_remaining(&self, remaining: usize) -> usize {
+        debug_assert!(remaining <= self.capacity);
+        match self.capacity.saturating_sub(remaining) {
+            position if position == self.capacity => 0,
+            position => position,
+        }
+    }
+
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+unsafe impl<B: WriteBuffer> WriteBuffer for Circular<B> {
+    type Word = B::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer()
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer_capacity()
+    }
+}
+
+/// A reader of the words written by DMA to a ring in circular mode, e.g. continuous ADC capture.
+///
+/// Each call to [`advance`](DmaRingWriter::advance) returns the words produced since the previous
+/// call. The writer can't detect the DMA overtaking the reader, it must be called at least once
+/// per pass through the ring, e.g. on the half and full transfer interrupts.
+pub struct DmaRingWriter<B: WriteBuffer> {
+    ring: Circular<B>,
+    ptr: *mut B::Word,
+    read: usize,
+}
+
+impl<B: WriteBuffer> DmaRingWriter<B> {
+    /// Use `buf` as a ring, with the DMA starting at its first word.
+    pub fn new(mut buf: B) -> Self {
+        let (ptr, _) = unsafe { buf.write_buffer() };
+        DmaRingWriter {
+            ring: Circular::new(buf),
+            ptr,
+            read: 0,
+        }
+    }
+
+    /// Return the words written since the last call, given the remaining transfer count of the
+    /// DMA.
+    ///
+    /// If the DMA wrapped around since the last call, the returned words end at the end of the
+    /// ring, and the words written from its start are returned by the next call.
+    ///
+    /// # Safety
+    ///
+    /// `remaining` must be read from the live transfer count of the DMA, so that all returned
+    /// words have been written, and thus initialized, by it. The DMA must not overwrite them
+    /// while the returned slice is in use, i.e. the slice must be dropped before the DMA wraps
+    /// around to them again.
+    pub unsafe fn advance(&mut self, remaining: usize) -> &[B::Word] {
+        let position = self.ring.position_from_remaining(remaining);
+        let start = self.read;
+        let end = if position < start {
+            self.ring.capacity()
+        } else {
+            position
+        };
+        self.read = if end == self.ring.capacity() { 0 } else { end };
+        core::slice::from_raw_parts(self.ptr.add(start), end - start)
+    }
+
+    /// Consume the writer, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.ring.into_inner()
+    }
+}
+
+unsafe impl<B: WriteBuffer> WriteBuffer for DmaRingWriter<B> {
+    type Word = B::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.ring.write_buffer()
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.ring.write_buffer_capacity()
+    }
+}
+
+/// A DMA buffer reinterpreted as a buffer of another word type `W`.
+///
+/// E.g. a `[u8; 64]` buffer can be moved by a DMA channel as 16 `u32` words, given that it is
+/// suitably aligned.
+pub struct ReinterpretWords<B, W> {
+    buffer: B,
+    len: usize,
+    _word: PhantomData<W>,
+}
+
+impl<B: ReadBuffer, W: Word> ReinterpretWords<B, W> {
+    /// Reinterpret the words of `buffer` as `W`.
+    ///
+    /// Returns `None` if the buffer reported by [`ReadBuffer::read_buffer`] isn't aligned for `W`,
+    /// or if its size isn't a multiple of the size of `W`.
+    pub fn new(buffer: B) -> Option<Self> {
+        let (ptr, len) = unsafe { buffer.read_buffer() };
+        Self::checked(
+            buffer,
+            ptr as usize,
+            len.checked_mul(mem::size_of::<B::Word>())?,
+        )
+    }
+}
+
+impl<B: WriteBuffer, W: Word> ReinterpretWords<B, W> {
+    /// Reinterpret the words of `buffer` as `W`.
+    ///
+    /// Returns `None` if the buffer reported by [`WriteBuffer::write_buffer`] isn't aligned for
+    /// `W`, or if its size isn't a multiple of the size of `W`.
+    pub fn new_mut(mut buffer: B) -> Option<Self> {
+        let (ptr, len) = unsafe { buffer.write_buffer() };
+        Self::checked(
+            buffer,
+            ptr as usize,
+            len.checked_mul(mem::size_of::<B::Word>())?,
+        )
+    }
+}
+
+impl<B, W> ReinterpretWords<B, W> {
+    fn checked(buffer: B, addr: usize, bytes: usize) -> Option<Self> {
+        let size = mem::size_of::<W>();
+        if addr % mem::align_of::<W>() != 0 || bytes.checked_rem(size)? != 0 {
+            return None;
+        }
+
+        Some(ReinterpretWords {
+            buffer,
+            len: bytes / size,
+            _word: PhantomData,
+        })
+    }
+
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+unsafe impl<B: ReadBuffer, W: Word> ReadBuffer for ReinterpretWords<B, W> {
+    type Word = W;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        let (ptr, _) = self.buffer.read_buffer();
+        (ptr as *const W, self.len)
+    }
+}
+
+unsafe impl<B: WriteBuffer, W: Word> WriteBuffer for ReinterpretWords<B, W> {
+    type Word = W;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        let (ptr, _) = self.buffer.write_buffer();
+        (ptr as *mut W, self.len)
+    }
+}
+
+/// Byte order of multi-byte words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endian {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
+impl Endian {
+    /// The byte order of the target.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Endian = Endian::Little;
+    /// The byte order of the target.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Endian = Endian::Big;
+}
+
+/// Trait for the byte order markers in [`endian`], used by [`Tagged`].
+pub trait ByteOrder {
+    /// The byte order represented by the marker.
+    const ENDIAN: Endian;
+}
+
+/// Byte order marker types for [`Tagged`].
+pub mod endian {
+    use super::{ByteOrder, Endian};
+
+    /// Little endian byte order.
+    #[derive(Clone, Copy, Debug)]
+    pub enum Little {}
+
+    /// Big endian byte order.
+    #[derive(Clone, Copy, Debug)]
+    pub enum Big {}
+
+    impl ByteOrder for Little {
+        const ENDIAN: Endian = Endian::Little;
+    }
+
+    impl ByteOrder for Big {
+        const ENDIAN: Endian = Endian::Big;
+    }
+}
+
+/// A DMA buffer tagged with the byte order `E` its words are meant to be in, one of the markers
+/// in [`endian`].
+///
+/// The buffer is forwarded unchanged, no bytes are swapped. Drivers can compare
+/// [`ENDIAN`](Tagged::ENDIAN) with [`Endian::NATIVE`] to decide whether to swap, e.g. with a
+/// byte swapping option of the DMA controller.
+pub struct Tagged<B, E> {
+    buffer: B,
+    _endian: PhantomData<E>,
+}
+
+impl<B, E: ByteOrder> Tagged<B, E> {
+    /// The byte order the buffer is tagged with.
+    pub const ENDIAN: Endian = E::ENDIAN;
+
+    /// Tag `buffer` with the byte order `E`.
+    pub fn new(buffer: B) -> Self {
+        Tagged {
+            buffer,
+            _endian: PhantomData,
+        }
+    }
+
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+unsafe impl<B: ReadBuffer, E> ReadBuffer for Tagged<B, E> {
+    type Word = B::Word;
+
+    const LEN: Option<usize> = B::LEN;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        self.buffer.read_buffer()
+    }
+}
+
+unsafe impl<B: WriteBuffer, E> WriteBuffer for Tagged<B, E> {
+    type Word = B::Word;
+
+    const LEN: Option<usize> = B::LEN;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer()
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer_capacity()
+    }
+}
+
+/// A DMA buffer logging its pointer and length with `defmt` whenever it is provided for DMA.
+///
+/// Messages are logged at trace level, the pointer and length of the inner buffer are forwarded
+/// unchanged.
+#[cfg(feature = "defmt")]
+pub struct Logged<B> {
+    buffer: B,
+}
+
+#[cfg(feature = "defmt")]
+impl<B> Logged<B> {
+    /// Log the uses of `buffer`.
+    pub fn new(buffer: B) -> Self {
+        Logged { buffer }
+    }
+
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+#[cfg(feature = "defmt")]
+unsafe impl<B: ReadBuffer> ReadBuffer for Logged<B> {
+    type Word = B::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        let (ptr, len) = self.buffer.read_buffer();
+        defmt::trace!(
+            "DMA read buffer at {=usize:#x}, {=usize} words",
+            ptr as usize,
+            len
+        );
+        (ptr, len)
+    }
+}
+
+#[cfg(feature = "defmt")]
+unsafe impl<B: WriteBuffer> WriteBuffer for Logged<B> {
+    type Word = B::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        let (ptr, len) = self.buffer.write_buffer();
+        defmt::trace!(
+            "DMA write buffer at {=usize:#x}, {=usize} words",
+            ptr as usize,
+            len
+        );
+        (ptr, len)
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        let (ptr, len) = self.buffer.write_buffer_capacity();
+        defmt::trace!(
+            "DMA write buffer at {=usize:#x}, capacity of {=usize} words",
+            ptr as usize,
+            len
+        );
+        (ptr, len)
+    }
+}
+
+// Addresses are formatted in hex, unlike with the derived `Debug` impls.
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for BufferInfo {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "BufferInfo {{ addr: {=usize:#x}, words: {=usize}, word_size: {=usize}, bytes: {=usize} }}",
+            self.addr,
+            self.words,
+            self.word_size,
+            self.bytes
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for UnalignedError {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "UnalignedError {{ ptr: {=usize:#x}, required: {=usize} }}",
+            self.ptr,
+            self.required
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for OutOfRegionError {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "OutOfRegionError {{ addr: {=usize:#x}, bytes: {=usize} }}",
+            self.addr,
+            self.bytes
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for TooLargeError {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "TooLargeError {{ words: {=usize}, max: {=usize} }}",
+            self.words,
+            self.max
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for TooShortError {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "TooShortError {{ words: {=usize}, min: {=usize} }}",
+            self.words,
+            self.min
+        )
+    }
+}
+
+/// A DMA write buffer that is reused for many transfers, and can be reset in between.
+pub struct Reusable<B> {
+    buffer: B,
+}
+
+impl<B: WriteBuffer> Reusable<B> {
+    /// Reuse `buffer` for many transfers.
+    pub fn new(buffer: B) -> Self {
+        Reusable { buffer }
+    }
+
+    /// Zero the buffer, so data of a previous transfer doesn't leak into the next one.
+    pub fn reset(&mut self) {
+        unsafe {
+            let (ptr, len) = self.buffer.write_buffer_bytes();
+            ptr::write_bytes(ptr, 0, len);
+        }
+    }
+
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+unsafe impl<B: WriteBuffer> WriteBuffer for Reusable<B> {
+    type Word = B::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer()
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer_capacity()
+    }
+}
+
+/// A DMA buffer that panics if it is dropped without being released.
+///
+/// Buffers that aren't `'static`, such as stack buffers, are only sound to use as long as the
+/// transfer using them isn't forgotten. Wrapping them in a `StaticGuard` that the transfer
+/// releases on completion helps catching mistakes during testing, an early drop panics.
+pub struct StaticGuard<B> {
+    buffer: B,
+}
+
+impl<B> StaticGuard<B> {
+    /// Guard `buffer` until [`release`](StaticGuard::release) is called.
+    pub fn new(buffer: B) -> Self {
+        StaticGuard { buffer }
+    }
+
+    /// Disarm the guard, returning the inner buffer.
+    pub fn release(self) -> B {
+        let this = mem::ManuallyDrop::new(self);
+        unsafe { ptr::read(&this.buffer) }
+    }
+}
+
+impl<B> Drop for StaticGuard<B> {
+    fn drop(&mut self) {
+        panic!("DMA buffer dropped before being released");
+    }
+}
+
+unsafe impl<B: ReadBuffer> ReadBuffer for StaticGuard<B> {
+    type Word = B::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        self.buffer.read_buffer()
+    }
+}
+
+unsafe impl<B: WriteBuffer> WriteBuffer for StaticGuard<B> {
+    type Word = B::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer()
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer_capacity()
+    }
+}
+
+/// Direction marker of a [`DirectedBuffer`] used for DMA reads.
+#[derive(Clone, Copy, Debug)]
+pub enum ReadDir {}
+
+/// Direction marker of a [`DirectedBuffer`] used for DMA writes.
+#[derive(Clone, Copy, Debug)]
+pub enum WriteDir {}
+
+/// A DMA buffer restricted to the direction `D` at the type level, either [`ReadDir`] or
+/// [`WriteDir`].
+///
+/// Only the buffer trait of that direction is implemented, a buffer set up for reads can't be
+/// written to by accident:
+///
+/// ```compile_fail
+/// use embedded_dma::{DirectedBuffer, WriteBuffer};
+///
+/// static mut BUF: [u8; 16] = [0; 16];
+///
+/// let mut buffer = DirectedBuffer::read(unsafe { &mut *core::ptr::addr_of_mut!(BUF) });
+/// unsafe { buffer.write_buffer() };
+/// ```
+pub struct DirectedBuffer<B, D> {
+    buffer: B,
+    _direction: PhantomData<D>,
+}
+
+impl<B: ReadBuffer> DirectedBuffer<B, ReadDir> {
+    /// Use `buffer` for DMA reads.
+    pub fn read(buffer: B) -> Self {
+        DirectedBuffer {
+            buffer,
+            _direction: PhantomData,
+        }
+    }
+}
+
+impl<B: WriteBuffer> DirectedBuffer<B, WriteDir> {
+    /// Use `buffer` for DMA writes.
+    pub fn write(buffer: B) -> Self {
+        DirectedBuffer {
+            buffer,
+            _direction: PhantomData,
+        }
+    }
+}
+
+impl<B: WriteBuffer> DirectedBuffer<B, ReadDir> {
+    /// Switch the buffer to DMA writes.
+    ///
+    /// This must only be done once all transfers reading the buffer have completed.
+    pub fn into_write(self) -> DirectedBuffer<B, WriteDir> {
+        DirectedBuffer::write(self.buffer)
+    }
+}
+
+impl<B: ReadBuffer> DirectedBuffer<B, WriteDir> {
+    /// Switch the buffer to DMA reads.
+    ///
+    /// This must only be done once all transfers writing the buffer have completed.
+    pub fn into_read(self) -> DirectedBuffer<B, ReadDir> {
+        DirectedBuffer::read(self.buffer)
+    }
+}
+
+impl<B, D> DirectedBuffer<B, D> {
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+unsafe impl<B: ReadBuffer> ReadBuffer for DirectedBuffer<B, ReadDir> {
+    type Word = B::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        self.buffer.read_buffer()
+    }
+}
+
+unsafe impl<B: WriteBuffer> WriteBuffer for DirectedBuffer<B, WriteDir> {
+    type Word = B::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer()
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.write_buffer_capacity()
+    }
+}
+
+/// A DMA buffer at a fixed location, described by a pointer and a length.
+///
+/// This is useful for memory that isn't owned by a Rust object, e.g. a memory-mapped peripheral
+/// FIFO. The pointer and length are returned unchanged by the buffer traits.
+pub struct Fixed<W> {
+    ptr: NonNull<W>,
+    len: usize,
+}
+
+impl<W> Fixed<W> {
+    /// Create a buffer of `len` words starting at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// The memory of `len` words starting at `ptr` must be valid for DMA reads and writes, and
+    /// must stay valid at the same location for as long as the returned `Fixed` is in use.
+    pub unsafe fn new(ptr: NonNull<W>, len: usize) -> Self {
+        Fixed { ptr, len }
+    }
+
+    /// Create a buffer of `len` words starting at the raw pointer `ptr`, e.g. one received from C
+    /// code.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must not be null. Apart from that, the same as for [`new`](Fixed::new).
+    pub unsafe fn from_raw(ptr: *mut W, len: usize) -> Self {
+        debug_assert!(!ptr.is_null(), "DMA buffer pointer is null");
+        Fixed::new(NonNull::new_unchecked(ptr), len)
+    }
+}
+
+impl<W: Word> Fixed<W> {
+    /// Reinterpret `bytes` as a buffer of `W` words, aligned to at least `align` bytes.
+    ///
+    /// The buffer is also checked to be aligned for `W`, so `align` can be 1 if no alignment
+    /// beyond that of the word type is needed. Alignments that aren't a power of two are rejected
+    /// as misaligned.
+    pub fn try_from_bytes(bytes: &'static mut [u8], align: usize) -> Result<Self, BufferError> {
+        debug_assert!(is_valid_word_size::<W>(), "DMA word type is zero-sized");
+        let required = align.max(mem::align_of::<W>());
+        let ptr = bytes.as_mut_ptr();
+        UnalignedError::check(ptr as usize, required).map_err(BufferError::Misaligned)?;
+
+        let size = mem::size_of::<W>();
+        if bytes.len().checked_rem(size) != Some(0) {
+            return Err(BufferError::LengthNotMultiple {
+                len: bytes.len(),
+                word_size: size,
+            });
+        }
+
+        Ok(unsafe { Fixed::from_raw(ptr as *mut W, bytes.len() / size) })
+    }
+}
+
+/// Error returned by [`Fixed::try_from_bytes`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferError {
+    /// The bytes are not aligned as required.
+    Misaligned(UnalignedError),
+    /// The number of bytes is not a multiple of the word size.
+    LengthNotMultiple {
+        /// Number of bytes.
+        len: usize,
+        /// Size of the word type in bytes.
+        word_size: usize,
+    },
+}
+
+/// An opaque byte buffer, e.g. memory handed out by a C HAL as `*mut c_void`.
+///
+/// Create one with [`Fixed::from_raw`], after casting the pointer to `*mut u8`.
+pub type OpaqueBuffer = Fixed<u8>;
+
+unsafe impl<W: Word> ReadBuffer for Fixed<W> {
+    type Word = W;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        (self.ptr.as_ptr(), self.len)
+    }
+}
+
+unsafe impl<W: Word> WriteBuffer for Fixed<W> {
+    type Word = W;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        (self.ptr.as_ptr(), self.len)
+    }
+}
+
+/// Create a DMA write buffer of `len` words starting at `ptr`, e.g. a region described by a
+/// bootloader at runtime.
+///
+/// The returned buffer only stores the pointer and length and returns them unchanged. Null and
+/// misaligned pointers are caught by debug assertions.
+///
+/// # Safety
+///
+/// The same as for [`Fixed::from_raw`].
+pub unsafe fn raw_write_buffer<W: Word>(ptr: *mut W, len: usize) -> impl WriteBuffer<Word = W> {
+    debug_assert!(
+        ptr as usize % mem::align_of::<W>() == 0,
+        "DMA buffer is not aligned for its word type"
+    );
+    Fixed::from_raw(ptr, len)
+}
+
+/// A single word, streamed `count` times by DMA.
+///
+/// This is useful to fill memory with a constant, e.g. clearing a framebuffer to one color,
+/// without storing `count` copies of the value. The target reports the address of the stored
+/// word together with the count, so the DMA must be configured not to increment the memory
+/// address. As a target, it is used through a `'static` reference or a `Box`.
+pub struct Repeat<W> {
+    value: W,
+    count: usize,
+}
+
+impl<W> Repeat<W> {
+    /// Stream `value` `count` times.
+    ///
+    /// # Safety
+    ///
+    /// The buffer reports a length of `count` words, but only holds a single one. It must only be
+    /// used for transfers that don't increment the memory address.
+    pub const unsafe fn new(value: W, count: usize) -> Self {
+        Repeat { value, count }
+    }
+}
+
+unsafe impl<W: Word> ReadTarget for Repeat<W> {
+    type Word = W;
+
+    fn as_read_buffer(&self) -> (*const Self::Word, usize) {
+        (&self.value, self.count)
+    }
+}
+
+/// Marker trait for read buffers that reside in flash, or other read-only memory.
+///
+/// Drivers can bound on this to select a transfer path for flash, e.g. skipping cache
+/// maintenance or using a specific bus. Buffers in RAM don't implement it, even if they are
+/// `'static`:
+///
+/// ```compile_fail
+/// use embedded_dma::FlashBuffer;
+///
+/// fn flash_transfer<B: FlashBuffer>(_buffer: B) {}
+///
+/// flash_transfer(Box::new([0u8; 16]));
+/// ```
+///
+/// # Safety
+///
+/// The memory returned by [`ReadBuffer::read_buffer`] must be read-only memory.
+pub unsafe trait FlashBuffer: ReadBuffer {}
+
+/// A reference to a DMA read target in flash.
+///
+/// Use the [`flash!`] macro to create one for data in a `static`.
+#[derive(Clone, Copy)]
+pub struct Flash<T: ?Sized + 'static> {
+    data: &'static T,
+}
+
+impl<T: ?Sized> Flash<T> {
+    /// Use `data` as a buffer in flash.
+    ///
+    /// # Safety
+    ///
+    /// `data` must reside in read-only memory.
+    pub const unsafe fn new_unchecked(data: &'static T) -> Self {
+        Flash { data }
+    }
+}
+
+unsafe impl<T: ReadTarget + ?Sized> ReadBuffer for Flash<T> {
+    type Word = T::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        self.data.as_read_buffer()
+    }
+}
+
+unsafe impl<T: ReadTarget + ?Sized> FlashBuffer for Flash<T> {}
+
+/// Place a value in a `static` and return it as a [`Flash`] buffer.
+///
+/// Immutable statics are placed in the `.rodata` section, which the usual linker scripts for
+/// microcontrollers map to flash. Types with interior mutability, such as `UnsafeCell`, are
+/// placed in RAM and must not be used.
+///
+/// ```
+/// use embedded_dma::{flash, FlashBuffer};
+///
+/// let lut = flash!([u16; 4] = [0, 1, 4, 9]);
+/// # fn flash_transfer<B: FlashBuffer>(_buffer: B) {}
+/// flash_transfer(lut);
+/// ```
+#[macro_export]
+macro_rules! flash {
+    ($ty:ty = $value:expr) => {{
+        static DATA: $ty = $value;
+        unsafe { $crate::Flash::new_unchecked(&DATA) }
+    }};
+}
+
+/// Error returned when the buffers of a [`DuplexPair`] differ in length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthMismatch {
+    /// Length of the transmit buffer in words.
+    pub tx: usize,
+    /// Length of the receive buffer in words.
+    pub rx: usize,
+}
+
+/// A transmit and a receive buffer of equal length, for full-duplex transfers such as SPI.
+pub struct DuplexPair<R, W> {
+    tx: R,
+    rx: W,
+}
+
+impl<R: ReadBuffer, W: WriteBuffer> DuplexPair<R, W> {
+    /// Pair `tx` and `rx`.
+    ///
+    /// Returns a `LengthMismatch` if the lengths reported by [`ReadBuffer::read_buffer`] and
+    /// [`WriteBuffer::write_buffer`] differ.
+    pub fn new(tx: R, mut rx: W) -> Result<Self, LengthMismatch> {
+        let (_, tx_len) = unsafe { tx.read_buffer() };
+        let (_, rx_len) = unsafe { rx.write_buffer() };
+        if tx_len == rx_len {
+            Ok(DuplexPair { tx, rx })
+        } else {
+            Err(LengthMismatch {
+                tx: tx_len,
+                rx: rx_len,
+            })
+        }
+    }
+
+    /// The transmit buffer.
+    pub fn tx(&self) -> &R {
+        &self.tx
+    }
+
+    /// The receive buffer.
+    pub fn rx(&mut self) -> &mut W {
+        &mut self.rx
+    }
+
+    /// Consume the pair, returning the transmit and receive buffers.
+    pub fn into_inner(self) -> (R, W) {
+        (self.tx, self.rx)
+    }
+}
+
+/// A single buffer used as both source and destination of memory-to-memory transfers, e.g. in
+/// loopback tests.
+///
+/// [`split`](Loopback::split) divides the buffer into two halves that never overlap, the first
+/// one as DMA source and the second one as DMA destination. With an odd length, the last word is
+/// in neither half.
+///
+/// Like references, the halves are only buffers if they are `'static`, i.e. if the `Loopback`
+/// itself is borrowed for `'static`, e.g. because it is stored in a `static` or was leaked.
+/// Otherwise, the `Loopback` could be dropped while DMA is still using a forgotten transfer:
+///
+/// ```compile_fail
+/// use embedded_dma::{Loopback, ReadBuffer};
+///
+/// fn send<B: ReadBuffer>(_buffer: B) {}
+///
+/// static mut BUF: [u8; 8] = [0; 8];
+///
+/// let mut loopback = Loopback::new(unsafe { &mut *core::ptr::addr_of_mut!(BUF) });
+/// let (src, _) = loopback.split();
+/// send(src);
+/// ```
+pub struct Loopback<B: ReadWriteBuffer> {
+    buffer: B,
+    ptr: *mut B::Word,
+    half: usize,
+}
+
+impl<B: ReadWriteBuffer> Loopback<B> {
+    /// Use `buffer` as source and destination.
+    pub fn new(mut buffer: B) -> Self {
+        let (_, ptr, len) = unsafe { buffer.read_write_buffer() };
+        Loopback {
+            buffer,
+            ptr,
+            half: len / 2,
+        }
+    }
+
+    /// The length of each half in words.
+    pub fn half_len(&self) -> usize {
+        self.half
+    }
+
+    /// Split the buffer into the first half, as DMA source, and the second half, as DMA
+    /// destination.
+    pub fn split(&mut self) -> (LoopbackRead<'_, B::Word>, LoopbackWrite<'_, B::Word>) {
+        let read = LoopbackRead {
+            ptr: self.ptr,
+            len: self.half,
+            _buffer: PhantomData,
+        };
+        let write = LoopbackWrite {
+            ptr: unsafe { self.ptr.add(self.half) },
+            len: self.half,
+            _buffer: PhantomData,
+        };
+        (read, write)
+    }
+
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+/// The source half of a [`Loopback`] buffer.
+pub struct LoopbackRead<'a, W> {
+    ptr: *const W,
+    len: usize,
+    _buffer: PhantomData<&'a [W]>,
+}
+
+unsafe impl<W: Word> ReadBuffer for LoopbackRead<'static, W> {
+    type Word = W;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        (self.ptr, self.len)
+    }
+}
+
+/// The destination half of a [`Loopback`] buffer.
+pub struct LoopbackWrite<'a, W> {
+    ptr: *mut W,
+    len: usize,
+    _buffer: PhantomData<&'a mut [W]>,
+}
+
+unsafe impl<W: Word> WriteBuffer for LoopbackWrite<'static, W> {
+    type Word = W;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        (self.ptr, self.len)
+    }
+}
+
+/// Scratch memory for materializing generated data into a DMA source.
+///
+/// The arena is consumed by [`fill_from`](Arena::fill_from) and can be recovered from the
+/// returned view once the transfer is done.
+pub struct Arena<'a, W> {
+    slots: &'a mut [W],
+}
+
+impl<'a, W: Word> Arena<'a, W> {
+    /// Use `slots` as scratch memory.
+    pub fn new(slots: &'a mut [W]) -> Self {
+        Arena { slots }
+    }
+
+    /// The number of words the arena can hold.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Write the items of `iter` to the arena, returning a view of the written words.
+    ///
+    /// If `iter` yields more items than the arena can hold, the data is truncated to the capacity
+    /// and `iter` is not advanced past it. Pass `iter.by_ref()` to keep the rest of the items.
+    pub fn fill_from(self, iter: impl Iterator<Item = W>) -> ReadView<'a, W> {
+        let mut len = 0;
+        for (slot, word) in self.slots.iter_mut().zip(iter) {
+            *slot = word;
+            len += 1;
+        }
+
+        ReadView {
+            slots: self.slots,
+            len,
+        }
+    }
+}
+
+/// The words written to an [`Arena`], usable as DMA source.
+///
+/// Like references, the view is only a buffer if it is `'static`.
+pub struct ReadView<'a, W> {
+    slots: &'a mut [W],
+    len: usize,
+}
+
+impl<'a, W> ReadView<'a, W> {
+    /// The written words.
+    pub fn as_slice(&self) -> &[W] {
+        &self.slots[..self.len]
+    }
+
+    /// Consume the view, returning the arena for reuse.
+    pub fn into_arena(self) -> Arena<'a, W> {
+        Arena { slots: self.slots }
+    }
+}
+
+unsafe impl<W: Word> ReadBuffer for ReadView<'static, W> {
+    type Word = W;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        (self.slots.as_ptr(), self.len)
+    }
+}
+
+/// Address and length of a single buffer in a [`ScatterList`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Descriptor {
+    /// Address of the start of the buffer.
+    pub address: usize,
+    /// Buffer size in words.
+    pub len: usize,
+}
+
+/// Error returned when adding to a [`ScatterList`] or [`LliBuilder`] that is already full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FullError;
+
+/// A list of up to `N` buffer descriptors for scatter-gather DMA.
+///
+/// The list only records addresses and lengths, the buffers must stay valid and must not be
+/// accessed through `&mut self` methods as long as the descriptors are in use by DMA.
+#[derive(Clone, Debug)]
+pub struct ScatterList<const N: usize> {
+    descriptors: [Descriptor; N],
+    len: usize,
+}
+
+impl<const N: usize> ScatterList<N> {
+    /// Create an empty list.
+    pub const fn new() -> Self {
+        ScatterList {
+            descriptors: [Descriptor { address: 0, len: 0 }; N],
+            len: 0,
+        }
+    }
+
+    /// Record the pointer and length of `buffer` in the next descriptor.
+    ///
+    /// Returns a `FullError` if the list already holds `N` descriptors.
+    pub fn push<B: ReadBuffer>(&mut self, buffer: &B) -> Result<(), FullError> {
+        let descriptor = self.descriptors.get_mut(self.len).ok_or(FullError)?;
+        let (ptr, len) = unsafe { buffer.read_buffer() };
+        *descriptor = Descriptor {
+            address: ptr as usize,
+            len,
+        };
+        self.len += 1;
+        Ok(())
+    }
+
+    /// The descriptors recorded so far, in order.
+    pub fn descriptors(&self) -> &[Descriptor] {
+        &self.descriptors[..self.len]
+    }
+}
+
+impl<const N: usize> Default for ScatterList<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A linked-list DMA descriptor, built by an [`LliBuilder`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Lli {
+    /// Address of the start of the source buffer.
+    pub src: usize,
+    /// Buffer size in words.
+    pub len: usize,
+    /// Address of the next descriptor, or 0 for the last one. Not set by the builder.
+    pub next: usize,
+}
+
+/// A builder for linked-list DMA descriptors, stored in a backing array provided by the user.
+///
+/// The builder only fills in the source address and length of each descriptor. Linking them is
+/// left to the driver, as the format of the `next` field depends on the DMA controller. As with
+/// [`ScatterList`], the buffers must stay valid as long as the descriptors are in use by DMA.
+pub struct LliBuilder<'a> {
+    backing: &'a mut [Lli],
+    len: usize,
+}
+
+impl<'a> LliBuilder<'a> {
+    /// Create an empty list, stored in `backing`.
+    pub fn new(backing: &'a mut [Lli]) -> Self {
+        LliBuilder { backing, len: 0 }
+    }
+
+    /// Record the pointer and length of `buffer` in the next descriptor.
+    ///
+    /// Returns a `FullError` if all descriptors of the backing array are in use.
+    pub fn add<B: ReadBuffer>(&mut self, buffer: &B) -> Result<(), FullError> {
+        let lli = self.backing.get_mut(self.len).ok_or(FullError)?;
+        let (ptr, len) = unsafe { buffer.read_buffer() };
+        *lli = Lli {
+            src: ptr as usize,
+            len,
+            next: 0,
+        };
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Finish the list, returning the descriptors recorded so far, in order.
+    pub fn finish(self) -> &'a mut [Lli] {
+        &mut self.backing[..self.len]
+    }
+}
+
+/// Direction of a DMA transfer, as seen from the buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// DMA reads from the buffer.
+    Read,
+    /// DMA writes to the buffer.
+    Write,
+    /// DMA reads from and writes to the buffer.
+    ReadWrite,
+}
+
+/// Error returned when a [`TypedBuffer`] is used in a direction it doesn't support.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DirectionError {
+    /// Direction the buffer was created for.
+    pub direction: Direction,
+}
+
+/// A DMA buffer whose direction is only known at runtime.
+///
+/// Access is checked against the [`Direction`] the buffer was created for, read-write buffers
+/// allow both reads and writes.
+pub struct TypedBuffer<W> {
+    ptr: NonNull<W>,
+    len: usize,
+    direction: Direction,
+}
+
+impl<W: Word> TypedBuffer<W> {
+    /// Create a buffer that can only be read by DMA.
+    pub fn read(buffer: &'static [W]) -> Self {
+        TypedBuffer {
+            ptr: NonNull::from(buffer).cast(),
+            len: buffer.len(),
+            direction: Direction::Read,
+        }
+    }
+
+    /// Create a buffer that can only be written by DMA.
+    pub fn write(buffer: &'static mut [W]) -> Self {
+        TypedBuffer {
+            len: buffer.len(),
+            ptr: NonNull::from(buffer).cast(),
+            direction: Direction::Write,
+        }
+    }
+
+    /// Create a buffer that can be both read and written by DMA.
+    pub fn read_write(buffer: &'static mut [W]) -> Self {
+        TypedBuffer {
+            len: buffer.len(),
+            ptr: NonNull::from(buffer).cast(),
+            direction: Direction::ReadWrite,
+        }
+    }
+
+    /// The direction the buffer was created for.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Provide the buffer for DMA reads.
+    ///
+    /// Returns an error if the buffer was created for DMA writes only.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`ReadBuffer::read_buffer`].
+    pub unsafe fn read_buffer(&self) -> Result<(*const W, usize), DirectionError> {
+        match self.direction {
+            Direction::Read | Direction::ReadWrite => Ok((self.ptr.as_ptr(), self.len)),
+            direction => Err(DirectionError { direction }),
+        }
+    }
+
+    /// Provide the buffer for DMA writes.
+    ///
+    /// Returns an error if the buffer was created for DMA reads only.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`WriteBuffer::write_buffer`].
+    pub unsafe fn write_buffer(&mut self) -> Result<(*mut W, usize), DirectionError> {
+        match self.direction {
+            Direction::Write | Direction::ReadWrite => Ok((self.ptr.as_ptr(), self.len)),
+            direction => Err(DirectionError { direction }),
+        }
+    }
+}
+
+/// Mock DMA buffer for testing drivers on the host.
+///
+/// The mock counts calls to the buffer trait methods, and tracks whether the buffer has been
+/// handed out to DMA. Calling [`contents_mut`](MockDmaBuffer::contents_mut) while the buffer is
+/// in use panics, as would be undefined behavior on a real buffer. A simulated transfer is ended
+/// with [`complete`](MockDmaBuffer::complete).
+#[cfg(feature = "test-utils")]
+pub struct MockDmaBuffer<W> {
+    data: alloc::vec::Vec<W>,
+    reads: core::cell::Cell<usize>,
+    writes: usize,
+    in_use: core::cell::Cell<bool>,
+}
+
+#[cfg(feature = "test-utils")]
+impl<W: Word> MockDmaBuffer<W> {
+    /// Create a mock buffer backed by `data`.
+    pub fn new(data: alloc::vec::Vec<W>) -> Self {
+        MockDmaBuffer {
+            data,
+            reads: core::cell::Cell::new(0),
+            writes: 0,
+            in_use: core::cell::Cell::new(false),
+        }
+    }
+
+    /// Number of times the buffer was provided for DMA reads.
+    pub fn read_calls(&self) -> usize {
+        self.reads.get()
+    }
+
+    /// Number of times the buffer was provided for DMA writes.
+    pub fn write_calls(&self) -> usize {
+        self.writes
+    }
+
+    /// Whether the buffer has been handed out to DMA and the transfer hasn't completed yet.
+    pub fn in_use(&self) -> bool {
+        self.in_use.get()
+    }
+
+    /// Mark the simulated transfer as complete.
+    pub fn complete(&mut self) {
+        self.in_use.set(false);
+    }
+
+    /// The contents of the buffer.
+    pub fn contents(&self) -> &[W] {
+        &self.data
+    }
+
+    /// The contents of the buffer, for modification.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is in use by DMA.
+    pub fn contents_mut(&mut self) -> &mut [W] {
+        assert!(!self.in_use(), "DMA buffer modified while in use");
+        &mut self.data
+    }
+}
+
+#[cfg(feature = "test-utils")]
+unsafe impl<W: Word> ReadBuffer for MockDmaBuffer<W> {
+    type Word = W;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        self.reads.set(self.reads.get() + 1);
+        self.in_use.set(true);
+        (self.data.as_ptr(), self.data.len())
+    }
+}
+
+#[cfg(feature = "test-utils")]
+unsafe impl<W: Word> WriteBuffer for MockDmaBuffer<W> {
+    type Word = W;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.writes += 1;
+        self.in_use.set(true);
+        (self.data.as_mut_ptr(), self.data.len())
+    }
+}
+
+/// Assert the core invariants of a [`ReadBuffer`] implementation, for testing custom impls.
+///
+/// This checks that the pointer is non-null, that the buffer size in bytes doesn't exceed
+/// `isize::MAX`, and that `read_buffer` returns the same value when called twice.
+///
+/// # Panics
+///
+/// Panics if any of the invariants doesn't hold.
+#[cfg(feature = "test-utils")]
+pub fn assert_read_buffer_invariants<B: ReadBuffer>(buffer: &B) {
+    let (ptr, len) = unsafe { buffer.read_buffer() };
+    assert!(!ptr.is_null(), "DMA buffer pointer is null");
+    let bytes = len.checked_mul(mem::size_of::<B::Word>());
+    assert!(
+        matches!(bytes, Some(bytes) if bytes <= isize::MAX as usize),
+        "DMA buffer size in bytes exceeds isize::MAX"
+    );
+    assert!(
+        unsafe { buffer.read_buffer() } == (ptr, len),
+        "DMA buffer changed between calls to read_buffer"
+    );
+}
+
+/// Assert that a [`WriteBuffer`] returns the same value from `n` calls to `write_buffer`, for
+/// testing custom impls.
+///
+/// ```
+/// # #[cfg(feature = "test-utils")] {
+/// use embedded_dma::assert_write_buffer_stable;
+///
+/// static mut BUF: [u8; 16] = [0; 16];
+///
+/// let mut buffer = unsafe { &mut *core::ptr::addr_of_mut!(BUF) };
+/// assert_write_buffer_stable!(buffer, 4);
+/// # }
+/// ```
+#[cfg(feature = "test-utils")]
+#[macro_export]
+macro_rules! assert_write_buffer_stable {
+    ($buffer:expr, $n:expr) => {{
+        let buffer = &mut $buffer;
+        let first = unsafe { $crate::WriteBuffer::write_buffer(buffer) };
+        for _ in 1..$n {
+            assert!(
+                unsafe { $crate::WriteBuffer::write_buffer(buffer) } == first,
+                "DMA buffer changed between calls to write_buffer"
+            );
+        }
+    }};
+}
+
+/// Fill the bytes of `buffer` with `sentinel`, to check for overruns of a simulated transfer.
+///
+/// After the transfer, [`PoisonGuard::verify`] checks that the bytes past the claimed region
+/// still hold the sentinel.
+#[cfg(feature = "test-utils")]
+pub fn poison_and_check<B: WriteBuffer>(buffer: &mut B, sentinel: u8) -> PoisonGuard {
+    let (ptr, len) = unsafe { buffer.write_buffer_bytes() };
+    unsafe { ptr::write_bytes(ptr, sentinel, len) };
+    PoisonGuard { ptr, len, sentinel }
+}
+
+/// Poisoned buffer returned by [`poison_and_check`].
+#[cfg(feature = "test-utils")]
+pub struct PoisonGuard {
+    ptr: *mut u8,
+    len: usize,
+    sentinel: u8,
+}
+
+#[cfg(feature = "test-utils")]
+impl PoisonGuard {
+    /// Check that only the first `written` bytes of the buffer have been overwritten.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any byte past `written` doesn't hold the sentinel anymore.
+    ///
+    /// # Safety
+    ///
+    /// The buffer passed to [`poison_and_check`] must still be alive, at the same location.
+    pub unsafe fn verify(&self, written: usize) {
+        let bytes = core::slice::from_raw_parts(self.ptr, self.len);
+        if let Some(offset) = bytes
+            .iter()
+            .skip(written)
+            .position(|&byte| byte != self.sentinel)
+        {
+            panic!("DMA buffer overrun at byte {}", written + offset);
+        }
+    }
+}
+
+/// Size of a Cortex-M7 data cache line in bytes.
+#[cfg(feature = "cortex-m")]
+const CACHE_LINE_SIZE: usize = 32;
+
+/// Extend the given memory range to whole cache lines, returning the new start and size.
+#[cfg(feature = "cortex-m")]
+fn cache_line_range(addr: usize, size: usize) -> (usize, usize) {
+    if size == 0 {
+        return (addr, 0);
+    }
+
+    // Computed relative to the start, so buffers at the end of the address space don't overflow.
+    let start = addr & !(CACHE_LINE_SIZE - 1);
+    let size = (addr - start).saturating_add(size);
+    match size % CACHE_LINE_SIZE {
+        0 => (start, size),
+        rem => (start, size.saturating_add(CACHE_LINE_SIZE - rem)),
+    }
+}
+
+/// Data cache maintenance for DMA buffers on Cortex-M cores with a data cache, such as the
+/// Cortex-M7.
+///
+/// Cache maintenance operates on whole cache lines, so the buffer range is extended to cache
+/// line boundaries. This trait is implemented for all types, its methods are available for
+/// [`ReadBuffer`]s and [`WriteBuffer`]s respectively.
+#[cfg(feature = "cortex-m")]
+pub trait CacheMaintenance {
+    /// Clean the data cache for the buffer, so that DMA reads see data written by the CPU.
+    ///
+    /// This must be called before starting a transfer reading from the buffer.
+    fn clean_for_dma(&self, scb: &mut cortex_m::peripheral::SCB)
+    where
+        Self: ReadBuffer,
+    {
+        let (ptr, len) = unsafe { self.read_buffer_bytes() };
+        let (addr, size) = cache_line_range(ptr as usize, len);
+        scb.clean_dcache_by_address(addr, size);
+    }
+
+    /// Invalidate the data cache for the buffer, so that the CPU sees data written by DMA.
+    ///
+    /// This must be called after a transfer writing to the buffer has completed.
+    ///
+    /// # Safety
+    ///
+    /// Whole cache lines are invalidated. Data of other objects sharing a cache line with the
+    /// buffer, that hasn't been written back to memory yet, is lost. Buffers should be aligned
+    /// to and sized in multiples of the cache line size to avoid this.
+    unsafe fn invalidate_for_dma(&mut self, scb: &mut cortex_m::peripheral::SCB)
+    where
+        Self: WriteBuffer,
+    {
+        let (ptr, len) = self.write_buffer_bytes();
+        let (addr, size) = cache_line_range(ptr as usize, len);
+        scb.invalidate_dcache_by_address(addr, size);
+    }
+}
+
+#[cfg(feature = "cortex-m")]
+impl<B: ?Sized> CacheMaintenance for B {}
+
+/// Trait for DMA word types used by the blanket DMA buffer impls.
+///
+/// # Safety
+///
+/// Types that implement this trait must be valid for every possible byte
+/// pattern. This is to ensure that, whatever DMA writes into the buffer,
+/// we won't get UB due to invalid values.
+///
+/// Zero-sized types must not be used as words, the default [`ReadTarget`] and [`WriteTarget`]
+/// methods can't compute a buffer length for them and will panic:
+///
+/// ```should_panic
+/// use embedded_dma::{ReadTarget, Word};
+///
+/// #[derive(Clone, Copy)]
+/// struct Nothing;
+///
+/// unsafe impl Word for Nothing {}
+///
+/// [Nothing; 4].as_read_buffer();
+/// ```
+///
+/// # Implementing `Word`
+///
+/// This trait is deliberately not sealed, so that HALs and applications can provide their own
+/// word types. An impl can't be checked by the compiler though, so it must only be written for
+/// types that uphold the requirements above, e.g. a newtype over an existing word:
+///
+/// ```
+/// use embedded_dma::{is_valid_word_size, Word};
+///
+/// #[derive(Clone, Copy)]
+/// #[repr(transparent)]
+/// struct Sample(u16);
+///
+/// unsafe impl Word for Sample {}
+///
+/// assert!(is_valid_word_size::<Sample>());
+/// ```
+///
+/// Types like `bool`, `char`, enums or references are not valid for every byte pattern and are
+/// not words:
+///
+/// ```compile_fail
+/// fn assert_word<W: embedded_dma::Word>() {}
+///
+/// assert_word::<bool>();
+/// ```
+///
+/// ```compile_fail
+/// fn assert_word<W: embedded_dma::Word>() {}
+///
+/// assert_word::<char>();
+/// ```
+///
+/// Implementing the trait always requires an explicit `unsafe impl`:
+///
+/// ```compile_fail
+/// struct Sample(u16);
+///
+/// impl embedded_dma::Word for Sample {}
+/// ```
+pub unsafe trait Word {}
+
+/// Check whether `W` has a size usable for DMA words, i.e. it is not zero-sized.
+///
+/// This can't tell whether `W` is valid for every byte pattern, but it is useful to catch
+/// obviously wrong [`Word`] impls in tests and debug assertions.
+pub const fn is_valid_word_size<W>() -> bool {
+    mem::size_of::<W>() != 0
+}
+
+/// Define a `repr(transparent)` newtype over a [`Word`] and implement `Word` for it.
+///
+/// The newtype is valid for every byte pattern because its only field is, so the impl is sound
+/// as long as the inner type is a `Word`. This is checked at compile time, together with the
+/// size of the newtype matching the inner type.
+///
+/// ```
+/// use embedded_dma::{transparent_word, Word};
+///
+/// transparent_word! {
+///     /// A single ADC sample.
+///     #[derive(Clone, Copy)]
+///     pub struct Sample(pub u16);
+/// }
+///
+/// fn assert_word<W: Word>() {}
+///
+/// assert_word::<Sample>();
+/// ```
+///
+/// Types that aren't words are rejected:
+///
+/// ```compile_fail
+/// embedded_dma::transparent_word! {
+///     struct Flag(bool);
+/// }
+/// ```
+#[macro_export]
+macro_rules! transparent_word {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident($field_vis:vis $inner:ty);) => {
+        $(#[$attr])*
+        #[repr(transparent)]
+        $vis struct $name($field_vis $inner);
+
+        const _: () = {
+            fn assert_word<W: $crate::Word>() {}
+            let _ = assert_word::<$inner>;
+        };
+
+        const _: [(); ::core::mem::size_of::<$inner>()] = [(); ::core::mem::size_of::<$name>()];
+
+        unsafe impl $crate::Word for $name {}
+    };
+}
+
+/// Define a `repr(C)` struct made of [`Word`] fields and implement `Word` for it.
+///
+/// This is the multi-field counterpart of [`transparent_word!`], e.g. for interleaved samples.
+/// Every field type is checked to be a `Word` at compile time. The struct must not contain
+/// padding, so the size of the struct is checked to be the sum of the sizes of its fields.
+///
+/// ```
+/// use embedded_dma::{dma_word, Word};
+///
+/// dma_word! {
+///     /// A stereo audio frame.
+///     #[derive(Clone, Copy)]
+///     pub struct Frame {
+///         pub left: i16,
+///         pub right: i16,
+///     }
+/// }
+///
+/// fn assert_word<W: Word>() {}
+///
+/// assert_word::<Frame>();
+/// ```
+///
+/// Fields that aren't words are rejected:
+///
+/// ```compile_fail
+/// embedded_dma::dma_word! {
+///     struct Status {
+///         code: u8,
+///         valid: bool,
+///     }
+/// }
+/// ```
+///
+/// So are structs with padding:
+///
+/// ```compile_fail
+/// embedded_dma::dma_word! {
+///     struct Padded {
+///         tag: u8,
+///         value: u32,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! dma_word {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($field_vis:vis $field:ident: $ty:ty),+ $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[repr(C)]
+        $vis struct $name {
+            $($field_vis $field: $ty),+
+        }
+
+        const _: () = {
+            fn assert_word<W: $crate::Word>() {}
+            $(let _ = assert_word::<$ty>;)+
+        };
+
+        const _: [(); 0 $(+ ::core::mem::size_of::<$ty>())+] = [(); ::core::mem::size_of::<$name>()];
+
+        unsafe impl $crate::Word for $name {}
+    };
+}
+
+unsafe impl Word for u8 {}
+unsafe impl Word for i8 {}
+unsafe impl Word for u16 {}
+unsafe impl Word for i16 {}
+unsafe impl Word for u32 {}
+unsafe impl Word for i32 {}
+unsafe impl Word for u64 {}
+unsafe impl Word for i64 {}
+unsafe impl Word for u128 {}
+unsafe impl Word for i128 {}
+unsafe impl Word for usize {}
+unsafe impl Word for isize {}
+unsafe impl Word for f32 {}
+unsafe impl Word for f64 {}
+unsafe impl<T: Word> Word for Wrapping<T> {}
+
+/// Marker trait for [`Word`]s of exactly `BYTES` bytes.
+///
+/// HALs can use this to check at compile time that the word type of a buffer matches the
+/// configured transfer width, e.g. requiring `B::Word: WordWidth<2>` for 16 bit transfers:
+///
+/// ```
+/// use embedded_dma::{ReadBuffer, WordWidth};
+///
+/// fn start_16bit_transfer<B>(_buffer: B)
+/// where
+///     B: ReadBuffer,
+///     B::Word: WordWidth<2>,
+/// {
+/// }
+///
+/// static SAMPLES: [u16; 4] = [0; 4];
+/// start_16bit_transfer(&SAMPLES);
+/// ```
+///
+/// ```compile_fail
+/// # use embedded_dma::{ReadBuffer, WordWidth};
+/// # fn start_16bit_transfer<B>(_buffer: B)
+/// # where
+/// #     B: ReadBuffer,
+/// #     B::Word: WordWidth<2>,
+/// # {
+/// # }
+/// static BYTES: [u8; 4] = [0; 4];
+/// start_16bit_transfer(&BYTES);
+/// ```
+///
+/// # Safety
+///
+/// The size of `Self` must be `BYTES`.
+pub unsafe trait WordWidth<const BYTES: usize>: Word {}
+
+unsafe impl WordWidth<1> for u8 {}
+unsafe impl WordWidth<1> for i8 {}
+unsafe impl WordWidth<2> for u16 {}
+unsafe impl WordWidth<2> for i16 {}
+unsafe impl WordWidth<4> for u32 {}
+unsafe impl WordWidth<4> for i32 {}
+unsafe impl WordWidth<4> for f32 {}
+unsafe impl WordWidth<8> for u64 {}
+unsafe impl WordWidth<8> for i64 {}
+unsafe impl WordWidth<8> for f64 {}
+unsafe impl WordWidth<16> for u128 {}
+unsafe impl WordWidth<16> for i128 {}
+#[cfg(target_pointer_width = "16")]
+unsafe impl WordWidth<2> for usize {}
+#[cfg(target_pointer_width = "16")]
+unsafe impl WordWidth<2> for isize {}
+#[cfg(target_pointer_width = "32")]
+unsafe impl WordWidth<4> for usize {}
+#[cfg(target_pointer_width = "32")]
+unsafe impl WordWidth<4> for isize {}
+#[cfg(target_pointer_width = "64")]
+unsafe impl WordWidth<8> for usize {}
+#[cfg(target_pointer_width = "64")]
+unsafe impl WordWidth<8> for isize {}
+unsafe impl<T: WordWidth<BYTES>, const BYTES: usize> WordWidth<BYTES> for Wrapping<T> {}
+
+// Atomic integers have the same layout as the underlying integer, and are valid for any bit
+// pattern. DMA accesses bypass the atomic operations though, so they don't synchronize with the
+// transfer. Software must still wait for the transfer to complete, e.g. with a fence, before
+// relying on the values.
+#[cfg(all(feature = "atomic", target_has_atomic = "8"))]
+unsafe impl Word for core::sync::atomic::AtomicU8 {}
+#[cfg(all(feature = "atomic", target_has_atomic = "8"))]
+unsafe impl Word for core::sync::atomic::AtomicI8 {}
+#[cfg(all(feature = "atomic", target_has_atomic = "16"))]
+unsafe impl Word for core::sync::atomic::AtomicU16 {}
+#[cfg(all(feature = "atomic", target_has_atomic = "16"))]
+unsafe impl Word for core::sync::atomic::AtomicI16 {}
+#[cfg(all(feature = "atomic", target_has_atomic = "32"))]
+unsafe impl Word for core::sync::atomic::AtomicU32 {}
+#[cfg(all(feature = "atomic", target_has_atomic = "32"))]
+unsafe impl Word for core::sync::atomic::AtomicI32 {}
+#[cfg(all(feature = "atomic", target_has_atomic = "64"))]
+unsafe impl Word for core::sync::atomic::AtomicU64 {}
+#[cfg(all(feature = "atomic", target_has_atomic = "64"))]
+unsafe impl Word for core::sync::atomic::AtomicI64 {}
+#[cfg(all(feature = "atomic", target_has_atomic = "ptr"))]
+unsafe impl Word for core::sync::atomic::AtomicUsize {}
+#[cfg(all(feature = "atomic", target_has_atomic = "ptr"))]
+unsafe impl Word for core::sync::atomic::AtomicIsize {}
+
+// The same applies to the atomics of `portable-atomic`, even if they are emulated with locks or
+// critical sections. DMA accesses race with accesses through the atomic API, so the transfer must
+// be synchronized with them by the caller.
+#[cfg(feature = "portable-atomic")]
+unsafe impl Word for portable_atomic::AtomicU8 {}
+#[cfg(feature = "portable-atomic")]
+unsafe impl Word for portable_atomic::AtomicI8 {}
+#[cfg(feature = "portable-atomic")]
+unsafe impl Word for portable_atomic::AtomicU16 {}
+#[cfg(feature = "portable-atomic")]
+unsafe impl Word for portable_atomic::AtomicI16 {}
+#[cfg(feature = "portable-atomic")]
+unsafe impl Word for portable_atomic::AtomicU32 {}
+#[cfg(feature = "portable-atomic")]
+unsafe impl Word for portable_atomic::AtomicI32 {}
+#[cfg(feature = "portable-atomic")]
+unsafe impl Word for portable_atomic::AtomicU64 {}
+#[cfg(feature = "portable-atomic")]
+unsafe impl Word for portable_atomic::AtomicI64 {}
+#[cfg(feature = "portable-atomic")]
+unsafe impl Word for portable_atomic::AtomicUsize {}
+#[cfg(feature = "portable-atomic")]
+unsafe impl Word for portable_atomic::AtomicIsize {}
+
+/// Wrapper that makes any [`bytemuck::Pod`] type usable as a DMA [`Word`].
+///
+/// `Pod` types are valid for any byte pattern, just like `Word` requires. A blanket
+/// `impl<T: Pod> Word for T` would conflict with the impls for the primitive types, so the
+/// wrapper is needed.
+#[cfg(feature = "bytemuck")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PodWord<T>(pub T);
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> Word for PodWord<T> {}
+
+/// Wrapper that makes any [`zerocopy::FromBytes`] type usable as a DMA [`Word`].
+///
+/// `FromBytes` types are valid for any byte pattern, so whatever DMA writes into a buffer of
+/// wrapped words is guaranteed to be a valid `T`.
+#[cfg(feature = "zerocopy")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct ZeroCopyWord<T>(pub T);
+
+#[cfg(feature = "zerocopy")]
+impl<T: zerocopy::FromBytes> ZeroCopyWord<T> {
+    /// Reinterpret a slice of `T` as a slice of wrapped words.
+    pub fn from_slice(slice: &[T]) -> &[Self] {
+        // Safety: `ZeroCopyWord<T>` is `repr(transparent)` over `T`.
+        unsafe { core::slice::from_raw_parts(slice.as_ptr() as *const Self, slice.len()) }
+    }
+
+    /// Reinterpret a mutable slice of `T` as a mutable slice of wrapped words.
+    pub fn from_mut_slice(slice: &mut [T]) -> &mut [Self] {
+        // Safety: `ZeroCopyWord<T>` is `repr(transparent)` over `T`.
+        unsafe { core::slice::from_raw_parts_mut(slice.as_mut_ptr() as *mut Self, slice.len()) }
+    }
+}
+
+#[cfg(feature = "zerocopy")]
+unsafe impl<T: zerocopy::FromBytes> Word for ZeroCopyWord<T> {}
+
+// Fixed-point numbers are `repr(transparent)` wrappers over a primitive integer.
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> Word for fixed::FixedI8<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> Word for fixed::FixedI16<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> Word for fixed::FixedI32<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> Word for fixed::FixedI64<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> Word for fixed::FixedI128<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> Word for fixed::FixedU8<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> Word for fixed::FixedU16<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> Word for fixed::FixedU32<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> Word for fixed::FixedU64<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> Word for fixed::FixedU128<Frac> {}
+
+/// Length in words of a target of `bytes` bytes at `ptr`, checking the word type and alignment
+/// in debug builds.
+fn target_len<W>(ptr: *const W, bytes: usize) -> usize {
+    debug_assert!(
+        is_valid_word_size::<W>(),
+        "zero-sized DMA words are not supported"
+    );
+    debug_assert!(
+        ptr as usize % mem::align_of::<W>() == 0,
+        "DMA buffer is not aligned for its word type"
+    );
+    bytes / mem::size_of::<W>()
+}
+
+/// Trait for `Deref` targets used by the blanket `DmaReadBuffer` impl.
+///
+/// This trait exists solely to work around
+/// https://github.com/rust-lang/rust/issues/20400.
+///
+/// # Safety
+///
+/// - `as_read_buffer` must adhere to the safety requirements
+///   documented for [`ReadBuffer::read_buffer`].
+/// - `LEN` must only be `Some(n)` if `as_read_buffer` returns a size of `n` words for every value
+///   of the type.
+pub unsafe trait ReadTarget {
+    type Word: Word;
+
+    /// Length in words, if it is the same for every value of the type.
+    const LEN: Option<usize> = None;
+
+    fn as_read_buffer(&self) -> (*const Self::Word, usize) {
+        let ptr = self as *const _ as *const Self::Word;
+        (ptr, target_len(ptr, mem::size_of_val(self)))
+    }
+
+    /// Alignment of the target in bytes.
+    fn alignment(&self) -> usize {
+        mem::align_of_val(self)
+    }
+}
+
+/// Trait for `DerefMut` targets used by the blanket `DmaWriteBuffer` impl.
+///
+/// This trait exists solely to work around
+/// https://github.com/rust-lang/rust/issues/20400.
+///
+/// # Safety
+///
+/// - `as_write_buffer` must adhere to the safety requirements
+///   documented for [`WriteBuffer::write_buffer`].
+/// - `LEN` must only be `Some(n)` if `as_write_buffer` returns a size of `n` words for every value
+///   of the type.
+pub unsafe trait WriteTarget {
+    type Word: Word;
+
+    /// Length in words, if it is the same for every value of the type.
+    const LEN: Option<usize> = None;
+
+    fn as_write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        let ptr = self as *mut _ as *mut Self::Word;
+        (ptr, target_len(ptr, mem::size_of_val(self)))
+    }
+
+    /// Full capacity of the target, see [`WriteBuffer::write_buffer_capacity`].
+    ///
+    /// Defaults to [`as_write_buffer`](WriteTarget::as_write_buffer). Targets that can hold more
+    /// words than they expose override it to cover their spare capacity.
+    fn as_write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.as_write_buffer()
+    }
+
+    /// Alignment of the target in bytes.
+    fn alignment(&self) -> usize {
+        mem::align_of_val(self)
+    }
+}
+
+unsafe impl<W: Word> ReadTarget for W {
+    type Word = W;
+
+    const LEN: Option<usize> = Some(1);
+}
+
+unsafe impl<W: Word> WriteTarget for W {
+    type Word = W;
+
+    const LEN: Option<usize> = Some(1);
+}
+
+unsafe impl<T: ReadTarget> ReadTarget for [T] {
+    type Word = T::Word;
+}
+
+unsafe impl<T: WriteTarget> WriteTarget for [T] {
+    type Word = T::Word;
+}
+
+/// Nested arrays resolve to the innermost word type, e.g. `[[u8; 16]; 8]` is a buffer of 128 `u8`
+/// words.
+unsafe impl<T: ReadTarget, const N: usize> ReadTarget for [T; N] {
+    type Word = T::Word;
+
+    const LEN: Option<usize> = match T::LEN {
+        Some(len) => Some(len * N),
+        None => None,
+    };
+}
+
+/// Nested arrays resolve to the innermost word type, e.g. `[[u8; 16]; 8]` is a buffer of 128 `u8`
+/// words.
+unsafe impl<T: WriteTarget, const N: usize> WriteTarget for [T; N] {
+    type Word = T::Word;
+
+    const LEN: Option<usize> = match T::LEN {
+        Some(len) => Some(len * N),
+        None => None,
+    };
+}
+
+/// The buffer must be fully initialized before it is handed to DMA for reading, ensuring this is
+/// up to the caller of [`ReadBuffer::read_buffer`].
+unsafe impl<T: ReadTarget> ReadTarget for MaybeUninit<T> {
+    type Word = T::Word;
+
+    const LEN: Option<usize> = T::LEN;
+}
+
+unsafe impl<T: WriteTarget> WriteTarget for MaybeUninit<T> {
+    type Word = T::Word;
+
+    const LEN: Option<usize> = T::LEN;
+}
+
+/// Get the words written by DMA to an uninitialized buffer, e.g. after a receive transfer
+/// reported its length.
+///
+/// # Panics
+///
+/// Panics if `written_words` is larger than `N`.
+///
+/// # Safety
+///
+/// The first `written_words` words of `buf` must have been initialized, e.g. by a completed DMA
+/// transfer into it.
+pub unsafe fn assume_written<T: Word, const N: usize>(
+    buf: &mut MaybeUninit<[T; N]>,
+    written_words: usize,
+) -> &[T] {
+    assert!(written_words <= N, "written length exceeds DMA buffer");
+    core::slice::from_raw_parts(buf.as_ptr() as *const T, written_words)
+}
+
+unsafe impl<T: ReadTarget> ReadTarget for UnsafeCell<T> {
+    type Word = T::Word;
+
+    const LEN: Option<usize> = T::LEN;
+
+    fn as_read_buffer(&self) -> (*const Self::Word, usize) {
+        let ptr = self.get() as *const Self::Word;
+        (ptr, target_len(ptr, mem::size_of_val(self)))
+    }
+}
+
+unsafe impl<T: WriteTarget> WriteTarget for UnsafeCell<T> {
+    type Word = T::Word;
+
+    const LEN: Option<usize> = T::LEN;
+
+    fn as_write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        let ptr = self.get() as *mut Self::Word;
+        (ptr, target_len(ptr, mem::size_of_val(self)))
+    }
+}
+
+unsafe impl<T: ReadTarget> ReadTarget for Cell<T> {
+    type Word = T::Word;
+
+    const LEN: Option<usize> = T::LEN;
+
+    fn as_read_buffer(&self) -> (*const Self::Word, usize) {
+        let ptr = self.as_ptr() as *const Self::Word;
+        (ptr, target_len(ptr, mem::size_of_val(self)))
+    }
+}
+
+/// DMA writes bypass `Cell`, so the contents change while the cell is shared. This is only sound
+/// if the cell isn't accessed, e.g. with `get` or `replace`, until the transfer is done.
+unsafe impl<T: WriteTarget> WriteTarget for Cell<T> {
+    type Word = T::Word;
+
+    const LEN: Option<usize> = T::LEN;
+
+    fn as_write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        let ptr = self.as_ptr() as *mut Self::Word;
+        (ptr, target_len(ptr, mem::size_of_val(self)))
+    }
+}
+
+// Tuples are used as a whole, so the length includes any padding between or after the fields.
+// Rust doesn't guarantee the order of the fields in memory either, so the layout of the buffer
+// must not be relied upon.
+unsafe impl<W: Word, A, B> ReadTarget for (A, B)
+where
+    A: ReadTarget<Word = W>,
+    B: ReadTarget<Word = W>,
+{
+    type Word = W;
+}
+
+unsafe impl<W: Word, A, B> WriteTarget for (A, B)
+where
+    A: WriteTarget<Word = W>,
+    B: WriteTarget<Word = W>,
+{
+    type Word = W;
+}
+
+unsafe impl<W: Word, A, B, C> ReadTarget for (A, B, C)
+where
+    A: ReadTarget<Word = W>,
+    B: ReadTarget<Word = W>,
+    C: ReadTarget<Word = W>,
+{
+    type Word = W;
+}
+
+unsafe impl<W: Word, A, B, C> WriteTarget for (A, B, C)
+where
+    A: WriteTarget<Word = W>,
+    B: WriteTarget<Word = W>,
+    C: WriteTarget<Word = W>,
+{
+    type Word = W;
+}
+
+/// Alignment marker types for [`Aligned`].
+pub mod alignment {
+    /// 4 byte alignment.
+    #[derive(Clone, Copy, Debug)]
+    #[repr(align(4))]
+    pub struct A4;
+
+    /// 8 byte alignment.
+    #[derive(Clone, Copy, Debug)]
+    #[repr(align(8))]
+    pub struct A8;
+
+    /// 16 byte alignment.
+    #[derive(Clone, Copy, Debug)]
+    #[repr(align(16))]
+    pub struct A16;
+
+    /// 32 byte alignment.
+    #[derive(Clone, Copy, Debug)]
+    #[repr(align(32))]
+    pub struct A32;
+
+    /// 64 byte alignment.
+    #[derive(Clone, Copy, Debug)]
+    #[repr(align(64))]
+    pub struct A64;
+}
+
+/// A DMA target aligned to the alignment of `A`, one of the markers in [`alignment`].
+///
+/// The target words are forwarded unchanged, any padding added for alignment is not part of the
+/// buffer.
+///
+/// ```
+/// use embedded_dma::{alignment::A32, Aligned};
+///
+/// static BUF: Aligned<A32, [u8; 64]> = Aligned::new([0; 64]);
+/// ```
+#[repr(C)]
+pub struct Aligned<A, T: ?Sized> {
+    _alignment: [A; 0],
+    value: T,
+}
+
+impl<A, T> Aligned<A, T> {
+    /// Align `value`.
+    pub const fn new(value: T) -> Self {
+        Aligned {
+            _alignment: [],
+            value,
+        }
+    }
+
+    /// Consume the wrapper, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<A, T: ?Sized> Deref for Aligned<A, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<A, T: ?Sized> DerefMut for Aligned<A, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+unsafe impl<A, T: ReadTarget + ?Sized> ReadTarget for Aligned<A, T> {
+    type Word = T::Word;
+
+    const LEN: Option<usize> = T::LEN;
+
+    fn as_read_buffer(&self) -> (*const Self::Word, usize) {
+        self.value.as_read_buffer()
+    }
+}
+
+unsafe impl<A, T: WriteTarget + ?Sized> WriteTarget for Aligned<A, T> {
+    type Word = T::Word;
+
+    const LEN: Option<usize> = T::LEN;
+
+    fn as_write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.value.as_write_buffer()
+    }
+
+    fn as_write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.value.as_write_buffer_capacity()
+    }
+}
+
+/// Trait for growable containers, such as `heapless::Vec`, used as DMA buffers in a [`Growable`].
+///
+/// Growable containers store their length next to their elements, so they aren't
+/// [`ReadTarget`]s or [`WriteTarget`]s. Arrays, cells or tuples of containers would hand the
+/// lengths to DMA as well.
+///
+/// # Safety
+///
+/// - `as_read_buffer` and `as_write_buffer` must return the initialized elements of the
+///   container, and `as_write_buffer_capacity` all elements it can hold without reallocating.
+/// - As long as the container isn't modified, the methods must adhere to the safety requirements
+///   documented for [`ReadBuffer::read_buffer`] and [`WriteBuffer::write_buffer`].
+pub unsafe trait GrowableTarget {
+    type Word: Word;
+
+    /// The initialized elements of the container.
+    fn as_read_buffer(&self) -> (*const Self::Word, usize);
+
+    /// The initialized elements of the container.
+    fn as_write_buffer(&mut self) -> (*mut Self::Word, usize);
+
+    /// All elements the container can hold, see [`WriteBuffer::write_buffer_capacity`].
+    fn as_write_buffer_capacity(&mut self) -> (*mut Self::Word, usize);
+}
+
+/// A growable container, such as `heapless::Vec`, behind a `'static` pointer.
+///
+/// Reads and writes cover the initialized elements only, i.e. the buffer length is `len()`.
+/// [`write_buffer_capacity`](WriteBuffer::write_buffer_capacity) covers all elements the container
+/// can hold, to receive into its spare capacity. The length of the container is not updated by
+/// the transfer, set it afterwards to expose the words written by DMA.
+pub struct Growable<B> {
+    buffer: B,
+}
+
+impl<B, T> Growable<B>
+where
+    B: Deref<Target = T> + StableDeref + 'static,
+    T: GrowableTarget + ?Sized,
+{
+    /// Use the container behind `buffer` for DMA.
+    pub fn new(buffer: B) -> Self {
+        Growable { buffer }
+    }
+}
+
+impl<B> Growable<B> {
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+unsafe impl<B, T> ReadBuffer for Growable<B>
+where
+    B: Deref<Target = T> + StableDeref + 'static,
+    T: GrowableTarget + ?Sized,
+{
+    type Word = T::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        self.buffer.as_read_buffer()
+    }
+}
+
+unsafe impl<B, T> WriteBuffer for Growable<B>
+where
+    B: DerefMut<Target = T> + StableDeref + 'static,
+    T: GrowableTarget + ?Sized,
+{
+    type Word = T::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.as_write_buffer()
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.buffer.as_write_buffer_capacity()
+    }
+}
+
+/// The capacity covers the whole backing array, i.e. `N` elements.
+#[cfg(feature = "heapless")]
+unsafe impl<W: Word, const N: usize> GrowableTarget for heapless::Vec<W, N> {
+    type Word = W;
+
+    fn as_read_buffer(&self) -> (*const Self::Word, usize) {
+        (self.as_ptr(), self.len())
+    }
+
+    fn as_write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        (self.as_mut_ptr(), self.len())
+    }
+
+    fn as_write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        (self.as_mut_ptr(), N)
+    }
+}
+
+/// The capacity covers the whole backing array, i.e. `N` elements.
+#[cfg(feature = "arrayvec")]
+unsafe impl<W: Word, const N: usize> GrowableTarget for arrayvec::ArrayVec<W, N> {
+    type Word = W;
+
+    fn as_read_buffer(&self) -> (*const Self::Word, usize) {
+        (self.as_ptr(), self.len())
+    }
+
+    fn as_write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        (self.as_mut_ptr(), self.len())
+    }
+
+    fn as_write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        (self.as_mut_ptr(), N)
+    }
+}
+
+/// The capacity covers the current allocation, inline or spilled.
+///
+/// The vector must not grow while it is in use by DMA. Growing it beyond its inline capacity
+/// moves the inline data to the heap, and growing spilled data reallocates it.
+#[cfg(feature = "smallvec")]
+unsafe impl<A: smallvec::Array> GrowableTarget for smallvec::SmallVec<A>
+where
+    A::Item: Word,
+{
+    type Word = A::Item;
+
+    fn as_read_buffer(&self) -> (*const Self::Word, usize) {
+        (self.as_ptr(), self.len())
+    }
+
+    fn as_write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        (self.as_mut_ptr(), self.len())
+    }
+
+    fn as_write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        (self.as_mut_ptr(), self.capacity())
+    }
+}
+
+#[cfg(feature = "generic-array")]
+unsafe impl<T: ReadTarget, N: generic_array::ArrayLength> ReadTarget
+    for generic_array::GenericArray<T, N>
+{
+    type Word = T::Word;
+}
+
+#[cfg(feature = "generic-array")]
+unsafe impl<T: WriteTarget, N: generic_array::ArrayLength> WriteTarget
+    for generic_array::GenericArray<T, N>
+{
+    type Word = T::Word;
+}
+
+/// A `bytes::Bytes` or `bytes::BytesMut` used as a DMA buffer by value.
+///
+/// The bytes live in a separate allocation, which doesn't move along with the handle. `Bytes` is
+/// usable for DMA reads, `BytesMut` for reads and writes. Writes cover the initialized bytes
+/// only, i.e. the buffer length is `len()`, while
+/// [`write_buffer_capacity`](WriteBuffer::write_buffer_capacity) covers the current allocation.
+#[cfg(feature = "bytes")]
+pub struct BytesBuffer<B> {
+    buffer: B,
+}
+
+#[cfg(feature = "bytes")]
+impl<B> BytesBuffer<B> {
+    /// Use `buffer` for DMA.
+    pub fn new(buffer: B) -> Self {
+        BytesBuffer { buffer }
+    }
+
+    /// Consume the wrapper, returning the inner buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+#[cfg(feature = "bytes")]
+unsafe impl ReadBuffer for BytesBuffer<bytes::Bytes> {
+    type Word = u8;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        (self.buffer.as_ptr(), self.buffer.len())
+    }
+}
+
+#[cfg(feature = "bytes")]
+unsafe impl ReadBuffer for BytesBuffer<bytes::BytesMut> {
+    type Word = u8;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        (self.buffer.as_ptr(), self.buffer.len())
+    }
+}
+
+#[cfg(feature = "bytes")]
+unsafe impl WriteBuffer for BytesBuffer<bytes::BytesMut> {
+    type Word = u8;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        (self.buffer.as_mut_ptr(), self.buffer.len())
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        (self.buffer.as_mut_ptr(), self.buffer.capacity())
+    }
+}
+
+/// A DMA buffer in a `static` `spin::Mutex`, accessed while holding the lock.
+///
+/// The lock is held for as long as the buffer is in use, which keeps other code from accessing
+/// the buffer during the transfer. It is released when the guard is dropped, so a transfer must
+/// only drop the buffer after it has completed.
+#[cfg(feature = "spin")]
+pub struct Locked<T: ?Sized + 'static> {
+    guard: spin::MutexGuard<'static, T>,
+}
+
+#[cfg(feature = "spin")]
+impl<T: ?Sized> Locked<T> {
+    /// Use the data protected by `guard` as a DMA buffer.
+    pub fn new(guard: spin::MutexGuard<'static, T>) -> Self {
+        Locked { guard }
+    }
+
+    /// Consume the buffer, returning the guard.
+    pub fn into_inner(self) -> spin::MutexGuard<'static, T> {
+        self.guard
+    }
+}
+
+#[cfg(feature = "spin")]
+unsafe impl<T: ReadTarget + ?Sized> ReadBuffer for Locked<T> {
+    type Word = T::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        self.guard.as_read_buffer()
+    }
+}
+
+#[cfg(feature = "spin")]
+unsafe impl<T: WriteTarget + ?Sized> WriteBuffer for Locked<T> {
+    type Word = T::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        self.guard.as_write_buffer()
+    }
+
+    unsafe fn write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+        self.guard.as_write_buffer_capacity()
+    }
+}
+
+/// A one-dimensional `ndarray` array with a contiguous, standard layout, usable as a DMA buffer.
+///
+/// Arrays with a stride other than one can't be described by a pointer and a length, so they are
+/// rejected when creating the buffer.
+#[cfg(feature = "ndarray")]
+pub struct ContiguousArray<S: ndarray::RawData> {
+    array: ndarray::ArrayBase<S, ndarray::Ix1>,
+}
+
+#[cfg(feature = "ndarray")]
+impl<S: ndarray::Data> ContiguousArray<S> {
+    /// Use `array` as a DMA buffer.
+    ///
+    /// Returns `None` if the array isn't contiguous in standard layout.
+    pub fn new(array: ndarray::ArrayBase<S, ndarray::Ix1>) -> Option<Self> {
+        array.as_slice()?;
+        Some(ContiguousArray { array })
+    }
+
+    /// Consume the buffer, returning the inner array.
+    pub fn into_inner(self) -> ndarray::ArrayBase<S, ndarray::Ix1> {
+        self.array
+    }
+}
+
+#[cfg(feature = "ndarray")]
+unsafe impl<W, S> ReadBuffer for ContiguousArray<S>
+where
+    W: Word,
+    S: ndarray::Data<Elem = W> + 'static,
+{
+    type Word = W;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        (self.array.as_ptr(), self.array.len())
+    }
+}
+
+#[cfg(feature = "ndarray")]
+unsafe impl<W, S> WriteBuffer for ContiguousArray<S>
+where
+    W: Word,
+    S: ndarray::DataMut<Elem = W> + 'static,
+{
+    type Word = W;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        (self.array.as_mut_ptr(), self.array.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::any::Any;
+    use core::ptr::addr_of_mut;
+
+    extern crate std;
+
+    fn api_read<W, B>(buffer: B) -> (*const W, usize)
+    where
+        B: ReadBuffer<Word = W>,
+    {
+        unsafe { buffer.read_buffer() }
+    }
+
+    fn api_write<W, B>(mut buffer: B) -> (*mut W, usize)
+    where
+        B: WriteBuffer<Word = W>,
+    {
+        unsafe { buffer.write_buffer() }
+    }
+
+    fn api_read_write<W, B>(mut buffer: B) -> (*const W, *mut W, usize)
+    where
+        B: ReadWriteBuffer<Word = W>,
+    {
+        unsafe { buffer.read_write_buffer() }
+    }
+
+    #[test]
+    fn read_api() {
+        const SIZE: usize = 128;
+        static BUF: [u8; SIZE] = [0u8; SIZE];
+
+        let (ptr, size_local) = api_read(&BUF);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u8>() });
+        assert_eq!(size_local, SIZE);
+    }
+
+    #[test]
+    fn write_api() {
+        const SIZE: usize = 128;
+        static mut BUF: [u8; SIZE] = [0u8; SIZE];
+
+        let (ptr, size_local) = api_write(unsafe { &mut *addr_of_mut!(BUF) });
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u8>() });
+        assert_eq!(size_local, SIZE);
+    }
+
+    #[test]
+    fn read_api_usize() {
+        let (ptr, size_local) = api_read(&[0usize; 4]);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<usize>() });
+        assert_eq!(size_local, 4);
+    }
+
+    #[test]
+    fn static_slices() {
+        static TABLE: [u16; 16] = [0; 16];
+        static mut BUF: [u8; 32] = [0; 32];
+
+        let table: &'static [u16] = &TABLE;
+        let (ptr, size_local) = api_read(table);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u16>() });
+        assert_eq!(size_local, 16);
+
+        let buf: &'static mut [u8] = unsafe { &mut *addr_of_mut!(BUF) };
+        let (ptr, size_local) = api_write(buf);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u8>() });
+        assert_eq!(size_local, 32);
+    }
+
+    #[test]
+    fn array_references() {
+        static TX: [u8; 32] = [0; 32];
+        static mut RX: [u8; 32] = [0; 32];
+
+        let tx: &'static [u8; 32] = &TX;
+        assert_eq!(api_read(tx), (TX.as_ptr(), 32));
+
+        let rx: &'static mut [u8; 32] = unsafe { &mut *addr_of_mut!(RX) };
+        let expected = (rx.as_mut_ptr(), 32);
+        assert_eq!(api_write(rx), expected);
+    }
+
+    #[test]
+    fn write_through_reference() {
+        fn reborrowed<B: WriteBuffer>(buffer: &mut B) -> (*mut B::Word, usize) {
+            unsafe { buffer.write_buffer() }
+        }
+
+        static mut RX: [u8; 32] = [0; 32];
+
+        let mut rx = unsafe { &mut *addr_of_mut!(RX) };
+        let expected = (rx.as_mut_ptr(), 32);
+        assert_eq!(reborrowed(&mut rx), expected);
+        assert_eq!(api_write(rx), expected);
+    }
+
+    #[test]
+    fn const_len() {
+        const TABLE_LEN: Option<usize> = <&'static mut [u8; 32] as WriteBuffer>::LEN;
+
+        assert_eq!(TABLE_LEN, Some(32));
+        assert_eq!(<&'static [[u16; 4]; 8] as ReadBuffer>::LEN, Some(32));
+        assert_eq!(<&'static mut MaybeUninit<u32> as WriteBuffer>::LEN, Some(1));
+        assert_eq!(<&'static mut [u8] as WriteBuffer>::LEN, None);
+        assert_eq!(<Fixed<u8> as ReadBuffer>::LEN, None);
+    }
+
+    #[test]
+    fn array_lengths() {
+        static mut BUF_64: [u8; 64] = [0; 64];
+        static mut BUF_300: [u8; 300] = [0; 300];
+        static mut BUF_1023: [u8; 1023] = [0; 1023];
+        static mut BUF_65537: [u8; 65537] = [0; 65537];
+
+        assert_eq!(api_write(unsafe { &mut *addr_of_mut!(BUF_64) }).1, 64);
+        assert_eq!(api_write(unsafe { &mut *addr_of_mut!(BUF_300) }).1, 300);
+        assert_eq!(api_write(unsafe { &mut *addr_of_mut!(BUF_1023) }).1, 1023);
+        assert_eq!(api_write(unsafe { &mut *addr_of_mut!(BUF_65537) }).1, 65537);
+    }
+
+    #[test]
+    fn nonnull_api() {
+        static mut BUF: [u32; 8] = [0; 8];
+
+        let mut buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let (ptr, len) = unsafe { buffer.read_buffer() };
+        let (nonnull, nonnull_len) = unsafe { buffer.read_buffer_nonnull() };
+        assert_eq!(nonnull.as_ptr() as *const u32, ptr);
+        assert_eq!(nonnull_len, len);
+
+        let (ptr, len) = unsafe { buffer.write_buffer() };
+        let (nonnull, nonnull_len) = unsafe { buffer.write_buffer_nonnull() };
+        assert_eq!(nonnull.as_ptr(), ptr);
+        assert_eq!(nonnull_len, len);
+    }
+
+    #[test]
+    fn sub_read() {
+        static BUF: [u16; 16] = [0; 16];
+
+        let sub = Sub::new(&BUF, 8, 8).unwrap();
+        let (ptr, size_local) = api_read(sub);
+        assert_eq!(ptr, BUF[8..].as_ptr());
+        assert_eq!(size_local, 8);
+
+        assert!(Sub::new(&BUF, 8, 9).is_none());
+        assert!(Sub::new(&BUF, 17, 0).is_none());
+        assert!(Sub::new(&BUF, usize::MAX, 2).is_none());
+    }
+
+    #[test]
+    fn sub_write() {
+        static mut BUF: [u8; 16] = [0; 16];
+
+        let buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let start = buffer.as_mut_ptr();
+        let sub = Sub::new_mut(buffer, 4, 12).unwrap();
+        let (ptr, size_local) = api_write(sub);
+        assert_eq!(ptr, unsafe { start.add(4) });
+        assert_eq!(size_local, 12);
+
+        let buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        assert!(Sub::new_mut(buffer, 4, 13).is_none());
+    }
+
+    #[test]
+    fn raw_byte_view() {
+        static BYTES: [u8; 16] = [0; 16];
+        static mut WORDS: [u32; 4] = [0; 4];
+
+        let (ptr, size_local) = api_read(RawByteView::new(&BYTES, 2, 14).unwrap());
+        assert_eq!(ptr, BYTES[2..].as_ptr());
+        assert_eq!(size_local, 14);
+        assert!(RawByteView::new(&BYTES, 2, 15).is_none());
+        assert!(RawByteView::new(&BYTES, 17, 0).is_none());
+        assert!(RawByteView::new(&BYTES, usize::MAX, 2).is_none());
+
+        let words = unsafe { &mut *addr_of_mut!(WORDS) };
+        let start = words.as_mut_ptr() as *mut u8;
+        let (ptr, size_local) = api_write(RawByteView::new_mut(words, 2, 14).unwrap());
+        assert_eq!(ptr, unsafe { start.add(2) });
+        assert_eq!(size_local, 14);
+    }
+
+    #[test]
+    fn sub_zero_length() {
+        static BUF: [u32; 4] = [0; 4];
+
+        let (ptr, size_local) = api_read(Sub::new(&BUF, 4, 0).unwrap());
+        assert_eq!(ptr, BUF.as_ptr_range().end);
+        assert_eq!(size_local, 0);
+    }
+
+    #[test]
+    fn sub_split_at() {
+        static BUF: [u32; 16] = [0; 16];
+
+        let sub = Sub::new(&BUF, 0, BUF.len()).unwrap();
+        let (first, second) = sub.split_at(6).unwrap();
+        let (first_ptr, first_len) = api_read(first);
+        let (second_ptr, second_len) = api_read(second);
+        assert_eq!(
+            second_ptr as usize - first_ptr as usize,
+            6 * mem::size_of::<u32>()
+        );
+        assert_eq!(first_len + second_len, BUF.len());
+
+        assert!(sub.split_at(16).is_some());
+        assert!(sub.split_at(17).is_none());
+    }
+
+    #[test]
+    fn sub_split_at_mut() {
+        static mut BUF: [u32; 16] = [0; 16];
+
+        let buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let base = buffer.as_mut_ptr();
+        let sub = Sub::new_mut(buffer, 2, 14).unwrap();
+        let (first, second) = sub.split_at_mut(6).unwrap();
+        assert_eq!(api_write(first), (unsafe { base.add(2) }, 6));
+        assert_eq!(api_write(second), (unsafe { base.add(8) }, 8));
+
+        let buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let sub = Sub::new_mut(buffer, 0, 16).unwrap();
+        assert!(sub.split_at_mut(17).is_none());
+    }
+
+    #[test]
+    fn word_size() {
+        assert_eq!(<&'static [u16; 4] as ReadBuffer>::WORD_SIZE, 2);
+        assert_eq!(<&'static mut [u16; 4] as WriteBuffer>::WORD_SIZE, 2);
+        assert_eq!(<&'static [u32] as ReadBuffer>::WORD_SIZE, 4);
+        assert_eq!(<&'static mut [u32] as WriteBuffer>::WORD_SIZE, 4);
+    }
+
+    #[test]
+    fn buffer_slice() {
+        static mut BUF: [u16; 12] = [0; 12];
+
+        let mut buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let slice = unsafe { buffer.read_buffer_slice() };
+        assert_eq!(slice as *const u16, buffer.as_ptr());
+        assert_eq!(unsafe { (&*slice).len() }, 12);
+
+        let slice = unsafe { buffer.write_buffer_slice() };
+        assert_eq!(slice as *mut u16, buffer.as_mut_ptr());
+        assert_eq!(unsafe { (&*slice).len() }, 12);
+    }
+
+    #[test]
+    fn buffer_range() {
+        static mut BUF: [u32; 8] = [0; 8];
+
+        let mut buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let (start, end) = unsafe { buffer.read_buffer_range() };
+        assert_eq!(start, buffer.as_ptr());
+        assert_eq!(end as usize - start as usize, 8 * 4);
+
+        let (start, end) = unsafe { buffer.write_buffer_range() };
+        assert_eq!(start, buffer.as_mut_ptr());
+        assert_eq!(end, unsafe { start.add(8) });
+    }
+
+    #[test]
+    fn as_bytes() {
+        static mut BUF: [u32; 4] = [0; 4];
+
+        let mut buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let ptr = buffer.as_mut_ptr() as *mut u8;
+        assert_eq!(unsafe { buffer.read_as_bytes() }, (ptr as *const u8, 16));
+        assert_eq!(unsafe { buffer.write_as_bytes() }, (ptr, 16));
+    }
+
+    #[test]
+    fn buffer_bytes() {
+        static mut BUF: [u32; 8] = [0; 8];
+
+        let mut buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let (ptr, len) = unsafe { buffer.read_buffer_bytes() };
+        assert_eq!(ptr, buffer.as_ptr() as *const u8);
+        assert_eq!(len, 32);
+
+        let (ptr, len) = unsafe { buffer.write_buffer_bytes() };
+        assert_eq!(ptr, buffer.as_mut_ptr() as *mut u8);
+        assert_eq!(len, 32);
+    }
+
+    #[test]
+    fn words_to_bytes() {
+        static BUF: [u16; 5] = [0; 5];
+
+        assert_eq!(Words(0).to_bytes::<u32>(), Bytes(0));
+        assert_eq!(Words(3).to_bytes::<u8>(), Bytes(3));
+        assert_eq!(Words(3).to_bytes::<u16>(), Bytes(6));
+        assert_eq!(Words(3).to_bytes::<[u32; 2]>(), Bytes(24));
+
+        let (ptr, words) = unsafe { (&BUF).read_buffer_words() };
+        assert_eq!((ptr, words), (BUF.as_ptr(), Words(5)));
+    }
+
+    #[test]
+    fn buffer_info() {
+        static mut BUF: [u16; 10] = [0; 10];
+
+        let mut buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let info = unsafe { buffer.read_buffer_info() };
+        assert_ne!(info.addr, 0);
+        assert_eq!(info.addr, buffer.as_ptr() as usize);
+        assert_eq!((info.words, info.word_size, info.bytes), (10, 2, 20));
+        assert_eq!(unsafe { buffer.write_buffer_info() }, info);
+    }
+
+    /// Buffer reporting more words than can be addressed in bytes.
+    struct Oversized;
+
+    unsafe impl ReadBuffer for Oversized {
+        type Word = u16;
+
+        unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+            (NonNull::dangling().as_ptr(), usize::MAX / 2 + 1)
+        }
+    }
+
+    #[test]
+    fn buffer_checked() {
+        static BUF: [u32; 4] = [0; 4];
+
+        assert_eq!(
+            unsafe { (&BUF).read_buffer_checked() },
+            Some((BUF.as_ptr(), 4))
+        );
+        assert_eq!(unsafe { Oversized.read_buffer_checked() }, None);
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic)]
+    fn buffer_bytes_overflow() {
+        let (_, len) = unsafe { Oversized.read_buffer_bytes() };
+        assert_eq!(len, usize::MAX);
+    }
+
+    #[test]
+    fn strided_buffer() {
+        static FRAME: [u16; 80 * 48] = [0; 80 * 48];
+        static mut CAPTURE: [u16; 80 * 48] = [0; 80 * 48];
+
+        let info = StrideInfo {
+            line_len: 64,
+            line_count: 48,
+            stride: 80,
+        };
+
+        let frame = StridedBuffer::new(&FRAME, 64, 48, 80).unwrap();
+        assert_eq!(
+            unsafe { frame.strided_read_buffer() },
+            (FRAME.as_ptr(), info)
+        );
+        assert!(StridedBuffer::new(&FRAME, 64, 48, 81).is_none());
+        assert!(StridedBuffer::new(&FRAME, 81, 48, 80).is_none());
+        assert!(StridedBuffer::new(&FRAME, 64, 0, usize::MAX).is_some());
+
+        let capture = unsafe { &mut *addr_of_mut!(CAPTURE) };
+        let ptr = capture.as_mut_ptr();
+        let mut capture = StridedBuffer::new_mut(capture, 64, 48, 80).unwrap();
+        assert_eq!(unsafe { capture.strided_write_buffer() }, (ptr, info));
+    }
+
+    #[test]
+    fn circular() {
+        static mut RING: [u8; 256] = [0; 256];
+
+        let ring = unsafe { &mut *addr_of_mut!(RING) };
+        let ptr = ring.as_mut_ptr();
+        let mut ring = Circular::new(ring);
+        assert_eq!(ring.capacity(), 256);
+        assert_eq!(ring.position_from_remaining(200), 56);
+        assert_eq!(ring.position_from_remaining(256), 0);
+        assert_eq!(ring.position_from_remaining(1), 255);
+        assert_eq!(unsafe { ring.write_buffer() }, (ptr, 256));
+    }
+
+    #[test]
+    fn ring_writer() {
+        static mut RING: [u8; 8] = [0; 8];
+
+        let mut ring = DmaRingWriter::new(unsafe { &mut *addr_of_mut!(RING) });
+        let (ptr, len) = unsafe { ring.write_buffer() };
+        assert_eq!(len, 8);
+        assert_eq!(unsafe { ring.write_buffer_capacity() }, (ptr, 8));
+        let dma = |words: core::ops::Range<usize>| {
+            for i in words {
+                unsafe { ptr.add(i % 8).write(i as u8) };
+            }
+        };
+
+        dma(0..6);
+        assert_eq!(unsafe { ring.advance(2) }, &[0, 1, 2, 3, 4, 5]);
+
+        dma(6..11);
+        assert_eq!(unsafe { ring.advance(5) }, &[6, 7]);
+        assert_eq!(unsafe { ring.advance(5) }, &[8, 9, 10]);
+        assert!(unsafe { ring.advance(5) }.is_empty());
+    }
+
+    #[test]
+    fn raw_write() {
+        static mut BUF: [u32; 8] = [0; 8];
+
+        let ptr = unsafe { &mut *addr_of_mut!(BUF) }.as_mut_ptr();
+        let (ptr_local, size_local) = api_write(unsafe { raw_write_buffer(ptr, 8) });
+        assert_eq!(ptr_local, ptr);
+        assert_eq!(size_local, 8);
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        should_panic(expected = "DMA buffer is not aligned for its word type")
+    )]
+    fn raw_write_misaligned() {
+        static mut BUF: [u32; 8] = [0; 8];
+
+        let ptr = unsafe { (addr_of_mut!(BUF) as *mut u8).add(1) as *mut u32 };
+        api_write(unsafe { raw_write_buffer(ptr, 4) });
+    }
+
+    #[test]
+    fn fixed() {
+        static mut FIFO: u32 = 0;
+
+        let fifo = unsafe { NonNull::new_unchecked(addr_of_mut!(FIFO)) };
+        let mut buffer = unsafe { Fixed::new(fifo, 1) };
+        assert_eq!(
+            unsafe { buffer.read_buffer() },
+            (fifo.as_ptr() as *const u32, 1)
+        );
+        assert_eq!(unsafe { buffer.write_buffer() }, (fifo.as_ptr(), 1));
+    }
+
+    #[test]
+    fn buffer_in_region() {
+        static mut BUF: [u32; 8] = [0; 8];
+
+        let mut buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let base = buffer.as_mut_ptr();
+        let region = MemoryRegion::new(base as usize, base as usize + 32);
+        assert_eq!(
+            unsafe { buffer.read_buffer_in_region(&region) },
+            Ok((base as *const u32, 8))
+        );
+        assert_eq!(
+            unsafe { buffer.write_buffer_in_region(&region) },
+            Ok((base, 8))
+        );
+
+        let region = MemoryRegion::new(base as usize, base as usize + 28);
+        let error = OutOfRegionError {
+            addr: base as usize,
+            bytes: 32,
+        };
+        assert_eq!(unsafe { buffer.read_buffer_in_region(&region) }, Err(error));
+
+        let ccm = unsafe { Fixed::from_raw(0x1000_0000 as *mut u32, 4) };
+        let sram = MemoryRegion::new(0x2000_0000, 0x2002_0000);
+        let error = OutOfRegionError {
+            addr: 0x1000_0000,
+            bytes: 16,
+        };
+        assert_eq!(unsafe { ccm.read_buffer_in_region(&sram) }, Err(error));
+        assert!(!MemoryRegion::new(0, usize::MAX).contains(usize::MAX, 1));
+    }
+
+    #[test]
+    fn lli_builder() {
+        static FIRST: [u16; 4] = [0; 4];
+        static SECOND: [u16; 8] = [0; 8];
+
+        let mut backing = [Lli::default(); 4];
+        let mut builder = LliBuilder::new(&mut backing);
+        builder.add(&&FIRST).unwrap();
+        builder.add(&&SECOND).unwrap();
+
+        let list = builder.finish();
+        assert_eq!(
+            list,
+            [
+                Lli {
+                    src: FIRST.as_ptr() as usize,
+                    len: 4,
+                    next: 0,
+                },
+                Lli {
+                    src: SECOND.as_ptr() as usize,
+                    len: 8,
+                    next: 0,
+                },
+            ]
+        );
+
+        let mut backing = [Lli::default(); 1];
+        let mut builder = LliBuilder::new(&mut backing);
+        assert_eq!(builder.add(&&FIRST), Ok(()));
+        assert_eq!(builder.add(&&SECOND), Err(FullError));
+        assert_eq!(builder.finish().len(), 1);
+    }
+
+    #[test]
+    fn endian_tagged() {
+        static BUF: [u16; 4] = [0; 4];
+        static mut RX: [u16; 4] = [0; 4];
+
+        type Tx = Tagged<&'static [u16; 4], endian::Big>;
+        assert_eq!(Tx::ENDIAN, Endian::Big);
+        assert_eq!(<Tx as ReadBuffer>::LEN, Some(4));
+        let (ptr, size_local) = api_read(Tx::new(&BUF));
+        assert_eq!(ptr, BUF.as_ptr());
+        assert_eq!(size_local, 4);
+
+        let rx = Tagged::<_, endian::Little>::new(unsafe { &mut *addr_of_mut!(RX) });
+        assert_eq!(
+            Tagged::<&mut [u16; 4], endian::Little>::ENDIAN,
+            Endian::Little
+        );
+        let (_, size_local) = api_write(rx);
+        assert_eq!(size_local, 4);
+    }
+
+    #[test]
+    fn fixed_from_bytes() {
+        static mut BUF: Aligned<alignment::A8, [u8; 16]> = Aligned::new([0; 16]);
+
+        let bytes = unsafe { &mut *addr_of_mut!(BUF) };
+        let base = bytes.as_write_buffer().0;
+        let mut fixed =
+            Fixed::<u32>::try_from_bytes(unsafe { &mut *base.cast::<[u8; 16]>() }, 8).unwrap();
+        assert_eq!(unsafe { fixed.write_buffer() }, (base as *mut u32, 4));
+
+        let unaligned = unsafe { core::slice::from_raw_parts_mut(base.add(4), 8) };
+        assert_eq!(
+            Fixed::<u32>::try_from_bytes(unaligned, 8).err(),
+            Some(BufferError::Misaligned(UnalignedError {
+                ptr: base as usize + 4,
+                required: 8,
+            }))
+        );
+
+        let odd = unsafe { core::slice::from_raw_parts_mut(base, 6) };
+        assert_eq!(
+            Fixed::<u32>::try_from_bytes(odd, 1).err(),
+            Some(BufferError::LengthNotMultiple {
+                len: 6,
+                word_size: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn arena() {
+        static mut SLOTS: [u16; 8] = [0; 8];
+
+        let slots = unsafe { &mut *addr_of_mut!(SLOTS) };
+        let base = slots.as_ptr();
+        let arena = Arena::new(slots);
+        assert_eq!(arena.capacity(), 8);
+        let view = arena.fill_from([1, 2, 3].iter().copied());
+        assert_eq!(view.as_slice(), &[1, 2, 3]);
+        let (ptr, size_local) = unsafe { view.read_buffer() };
+        assert_eq!(ptr, base);
+        assert_eq!(size_local, 3);
+
+        let mut words = 10..;
+        let view = view.into_arena().fill_from(words.by_ref());
+        assert_eq!(view.as_slice(), &[10, 11, 12, 13, 14, 15, 16, 17]);
+        assert_eq!(api_read(view).1, 8);
+        assert_eq!(words.next(), Some(18));
+    }
+
+    #[test]
+    fn buffer_aligned() {
+        #[repr(C, align(16))]
+        struct Aligned16([u32; 8]);
+
+        unsafe impl ReadTarget for Aligned16 {
+            type Word = u32;
+        }
+
+        unsafe impl WriteTarget for Aligned16 {
+            type Word = u32;
+        }
+
+        static mut BUF: Aligned16 = Aligned16([0; 8]);
+
+        let mut buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let base = buffer.0.as_mut_ptr();
+        assert_eq!(
+            unsafe { buffer.read_buffer_aligned(16) },
+            Ok((base as *const u32, 8))
+        );
+        assert_eq!(unsafe { buffer.write_buffer_aligned(16) }, Ok((base, 8)));
+
+        let mut sub = Sub::new_mut(buffer, 1, 4).unwrap();
+        let error = UnalignedError {
+            ptr: base as usize + 4,
+            required: 8,
+        };
+        assert_eq!(unsafe { sub.read_buffer_aligned(8) }, Err(error));
+        assert_eq!(unsafe { sub.write_buffer_aligned(8) }, Err(error));
+    }
+
+    #[test]
+    fn buffer_aligned_invalid() {
+        static mut BUF: [u32; 8] = [0; 8];
+
+        let mut buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        let base = buffer.as_mut_ptr() as usize;
+        for &align in &[0, 3] {
+            let error = UnalignedError {
+                ptr: base,
+                required: align,
+            };
+            assert_eq!(unsafe { buffer.read_buffer_aligned(align) }, Err(error));
+            assert_eq!(unsafe { buffer.write_buffer_aligned(align) }, Err(error));
+        }
+    }
+
+    #[test]
+    fn min_len() {
+        static SHORT: [u32; 2] = [0; 2];
+        static mut LONG: [u32; 8] = [0; 8];
+
+        let short = &SHORT;
+        assert_eq!(
+            unsafe { short.read_buffer_min_len(4) },
+            Err(TooShortError { words: 2, min: 4 })
+        );
+
+        let mut long = unsafe { &mut *addr_of_mut!(LONG) };
+        let ptr = long.as_mut_ptr();
+        assert_eq!(
+            unsafe { long.read_buffer_min_len(4) },
+            Ok((ptr as *const u32, 8))
+        );
+        assert_eq!(unsafe { long.write_buffer_min_len(8) }, Ok((ptr, 8)));
+        assert_eq!(
+            unsafe { long.write_buffer_min_len(9) },
+            Err(TooShortError { words: 8, min: 9 })
+        );
+    }
+
+    #[test]
+    fn narrow_count() {
+        static BUF: [u8; 65536] = [0; 65536];
+        static mut RX: [u16; 65535] = [0; 65535];
+
+        let buffer = &BUF;
+        assert_eq!(
+            unsafe { buffer.read_buffer_u16() },
+            Err(TooLargeError {
+                words: 65536,
+                max: 65535
+            })
+        );
+        assert_eq!(
+            unsafe { buffer.read_buffer_u32() },
+            Ok((BUF.as_ptr(), 65536))
+        );
+
+        let mut rx = unsafe { &mut *addr_of_mut!(RX) };
+        let ptr = rx.as_mut_ptr();
+        assert_eq!(unsafe { rx.write_buffer_u16() }, Ok((ptr, 65535)));
+        assert_eq!(unsafe { rx.write_buffer_u32() }, Ok((ptr, 65535)));
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn narrow_count_u32() {
+        let error = TooLargeError {
+            words: usize::MAX / 2 + 1,
+            max: u32::MAX as usize,
+        };
+        assert_eq!(unsafe { Oversized.read_buffer_u32() }, Err(error));
+    }
+
+    #[test]
+    fn read_chunks() {
+        static BUF: [u8; 70000] = [0; 70000];
+
+        let buffer = &BUF;
+        let mut chunks = unsafe { buffer.read_chunks(65535) };
+        assert_eq!(chunks.next(), Some((BUF.as_ptr(), 65535)));
+        assert_eq!(
+            chunks.next(),
+            Some((unsafe { BUF.as_ptr().add(65535) }, 4465))
+        );
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn typed_buffer() {
+        static TX: [u8; 4] = [0; 4];
+        static mut RX: [u8; 8] = [0; 8];
+        static mut BIDI: [u8; 2] = [0; 2];
+
+        let mut tx = TypedBuffer::read(&TX);
+        assert_eq!(tx.direction(), Direction::Read);
+        assert_eq!(unsafe { tx.read_buffer() }, Ok((TX.as_ptr(), 4)));
+        assert_eq!(
+            unsafe { tx.write_buffer() },
+            Err(DirectionError {
+                direction: Direction::Read
+            })
+        );
+
+        let rx_buf = unsafe { &mut *addr_of_mut!(RX) };
+        let rx_ptr = rx_buf.as_mut_ptr();
+        let mut rx = TypedBuffer::write(rx_buf);
+        assert_eq!(unsafe { rx.write_buffer() }, Ok((rx_ptr, 8)));
+        assert_eq!(
+            unsafe { rx.read_buffer() },
+            Err(DirectionError {
+                direction: Direction::Write
+            })
+        );
+
+        let bidi_buf = unsafe { &mut *addr_of_mut!(BIDI) };
+        let bidi_ptr = bidi_buf.as_mut_ptr();
+        let mut bidi = TypedBuffer::read_write(bidi_buf);
+        assert_eq!(
+            unsafe { bidi.read_buffer() },
+            Ok((bidi_ptr as *const u8, 2))
+        );
+        assert_eq!(unsafe { bidi.write_buffer() }, Ok((bidi_ptr, 2)));
+    }
+
+    #[test]
+    fn split_first_last() {
+        static BUF: [u32; 4] = [0; 4];
+        static ONE: [u32; 1] = [0];
+        static EMPTY: [u32; 0] = [];
+
+        let buf = &BUF;
+        let (first, rest) = unsafe { buf.read_buffer_split_first() }.unwrap();
+        assert_eq!(
+            (first, rest),
+            (&BUF[0] as *const u32, (BUF[1..].as_ptr(), 3))
+        );
+        let (last, rest) = unsafe { buf.read_buffer_split_last() }.unwrap();
+        assert_eq!((last, rest), (&BUF[3] as *const u32, (BUF.as_ptr(), 3)));
+
+        let (_, (_, len)) = unsafe { (&ONE).read_buffer_split_first() }.unwrap();
+        assert_eq!(len, 0);
+        assert!(unsafe { (&EMPTY).read_buffer_split_last() }.is_none());
+    }
+
+    #[test]
+    fn split_array() {
+        static BUF: [u32; 16] = [0; 16];
+        static ODD: [u8; 10] = [0; 10];
+
+        let buffer = &BUF;
+        let chunks = unsafe { buffer.split_array::<4>() };
+        let mut count = 0;
+        for (i, chunk) in chunks.enumerate() {
+            assert_eq!(chunk, (BUF[i * 4..].as_ptr(), 4));
+            count += 1;
+        }
+        assert_eq!(count, 4);
+
+        let buffer = &ODD;
+        let mut chunks = unsafe { buffer.split_array::<4>() };
+        assert_eq!(chunks.next(), Some((ODD.as_ptr(), 4)));
+        assert_eq!(chunks.next(), Some((ODD[4..].as_ptr(), 4)));
+        assert_eq!(chunks.next(), Some((ODD[8..].as_ptr(), 2)));
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn peripheral_register() {
+        fn api_peripheral<P: PeripheralAddress<Word = u16>>(peripheral: &P) -> (usize, bool) {
+            (peripheral.address(), peripheral.increment())
+        }
+
+        const DATA_REGISTER: PeripheralRegister<u16> =
+            unsafe { PeripheralRegister::new(0x4001_300c) };
+
+        assert_eq!(api_peripheral(&DATA_REGISTER), (0x4001_300c, false));
+    }
+
+    #[test]
+    fn read_write_api() {
+        const SIZE: usize = 64;
+        static mut BUF: [u16; SIZE] = [0u16; SIZE];
+
+        let (read_ptr, write_ptr, size_local) = api_read_write(unsafe { &mut *addr_of_mut!(BUF) });
+        assert!(unsafe { (&*read_ptr as &dyn Any).is::<u16>() });
+        assert_eq!(read_ptr, write_ptr as *const u16);
+        assert_eq!(size_local, SIZE);
+    }
+
+    #[test]
+    fn write_api_wrapping() {
+        static mut BUF: [Wrapping<u16>; 8] = [Wrapping(0); 8];
+
+        let (ptr, size_local) = api_write(unsafe { &mut *addr_of_mut!(BUF) });
+        assert!(unsafe { (&*ptr as &dyn Any).is::<Wrapping<u16>>() });
+        assert_eq!(size_local, 8);
+    }
+
+    #[test]
+    fn write_api_f32() {
+        static mut BUF: [f32; 16] = [0.0; 16];
+
+        let (ptr, size_local) = api_write(unsafe { &mut *addr_of_mut!(BUF) });
+        assert!(unsafe { (&*ptr as &dyn Any).is::<f32>() });
+        assert_eq!(size_local, 16);
+    }
+
+    #[test]
+    fn write_api_u128() {
+        static mut BUF: [u128; 2] = [0; 2];
+
+        let (ptr, size_local) = api_write(unsafe { &mut *addr_of_mut!(BUF) });
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u128>() });
+        assert_eq!(ptr as usize % mem::align_of::<u128>(), 0);
+        assert_eq!(size_local, 2);
+    }
+
+    #[test]
+    fn read_api_maybe_uninit() {
+        static BUF: MaybeUninit<[u8; 32]> = MaybeUninit::new([0; 32]);
+
+        let (ptr, size_local) = api_read(&BUF);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u8>() });
+        assert_eq!(size_local, 32);
+    }
+
+    #[test]
+    fn write_api_maybe_uninit_slice() {
+        static mut BUF: [MaybeUninit<u16>; 10] = [MaybeUninit::uninit(); 10];
+
+        let buf: &'static mut [MaybeUninit<u16>] = unsafe { &mut *addr_of_mut!(BUF) };
+        let (ptr, size_local) = api_write(buf);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u16>() });
+        assert_eq!(size_local, 10);
+    }
+
+    #[test]
+    fn write_api_maybe_uninit_array() {
+        static mut BUF: [MaybeUninit<u8>; 64] = [MaybeUninit::uninit(); 64];
+
+        let (ptr, size_local) = api_write(unsafe { &mut *addr_of_mut!(BUF) });
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u8>() });
+        assert_eq!(size_local, 64);
+    }
+
+    #[test]
+    fn maybe_uninit_written() {
+        let mut buf = MaybeUninit::<[u8; 16]>::uninit();
+
+        let (ptr, len) = buf.as_write_buffer();
+        assert_eq!(len, 16);
+        for i in 0..5 {
+            unsafe { ptr.add(i).write(i as u8) };
+        }
+
+        let written = unsafe { assume_written(&mut buf, 5) };
+        assert_eq!(written.len(), 5);
+        assert_eq!(written, &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "written length exceeds DMA buffer")]
+    fn maybe_uninit_written_too_long() {
+        let mut buf = MaybeUninit::<[u8; 16]>::uninit();
+        unsafe { assume_written(&mut buf, 17) };
+    }
+
+    #[test]
+    fn word_widths() {
+        fn width<W: WordWidth<BYTES>, const BYTES: usize>() -> usize {
+            assert_eq!(mem::size_of::<W>(), BYTES);
+            BYTES
+        }
+
+        assert_eq!(width::<u8, 1>(), 1);
+        assert_eq!(width::<i16, 2>(), 2);
+        assert_eq!(width::<f32, 4>(), 4);
+        assert_eq!(width::<Wrapping<u64>, 8>(), 8);
+        assert_eq!(
+            width::<usize, { mem::size_of::<usize>() }>(),
+            mem::size_of::<usize>()
+        );
+    }
+
+    #[cfg(feature = "atomic")]
+    #[test]
+    fn write_api_atomic() {
+        use core::sync::atomic::AtomicU32;
+
+        #[allow(clippy::declare_interior_mutable_const)]
+        const ZERO: AtomicU32 = AtomicU32::new(0);
+        static mut BUF: [AtomicU32; 4] = [ZERO; 4];
+
+        let (ptr, size_local) = api_write(unsafe { &mut *addr_of_mut!(BUF) });
+        assert!(unsafe { (&*ptr as &dyn Any).is::<AtomicU32>() });
+        assert_eq!(size_local, 4);
+    }
+
+    #[cfg(feature = "portable-atomic")]
+    #[test]
+    fn write_api_portable_atomic() {
+        use portable_atomic::AtomicU32;
+
+        static mut BUF: [AtomicU32; 2] = [AtomicU32::new(0), AtomicU32::new(0)];
+
+        assert_eq!(<&'static mut [AtomicU32; 2] as WriteBuffer>::WORD_SIZE, 4);
+        let (ptr, size_local) = api_write(unsafe { &mut *addr_of_mut!(BUF) });
+        assert!(unsafe { (&*ptr as &dyn Any).is::<AtomicU32>() });
+        assert_eq!(size_local, 2);
+    }
+
+    #[test]
+    fn loopback() {
+        use std::boxed::Box;
+
+        static mut BUF: [u32; 9] = [0; 9];
+
+        let buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        buffer[..4].copy_from_slice(&[1, 2, 3, 4]);
+        let loopback = Box::leak(Box::new(Loopback::new(buffer)));
+        assert_eq!(loopback.half_len(), 4);
+
+        let (src, mut dst) = loopback.split();
+        let (src, src_len) = unsafe { src.read_buffer() };
+        let (dst, dst_len) = unsafe { dst.write_buffer() };
+        assert_eq!(src_len, 4);
+        assert_eq!(dst_len, 4);
+        assert!(unsafe { src.add(src_len) } <= dst as *const u32);
+        unsafe { ptr::copy_nonoverlapping(src, dst, src_len) };
+
+        let copied = unsafe { core::slice::from_raw_parts(src, 9) };
+        assert_eq!(copied, &[1, 2, 3, 4, 1, 2, 3, 4, 0]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn loopback_vec() {
+        use std::boxed::Box;
+
+        let loopback = Box::leak(Box::new(Loopback::new(alloc::vec![0xABu8, 0xCD, 0, 0])));
+
+        let (src, dst) = loopback.split();
+        let (src, src_len) = api_read(src);
+        let (dst, _) = api_write(dst);
+        unsafe { ptr::copy_nonoverlapping(src, dst, src_len) };
+
+        let copied = unsafe { core::slice::from_raw_parts(src, 4) };
+        assert_eq!(copied, [0xAB, 0xCD, 0xAB, 0xCD]);
+    }
+
+    #[test]
+    fn read_api_custom_word() {
+        #[derive(Clone, Copy)]
+        struct Sample(#[allow(dead_code)] u16);
+
+        unsafe impl Word for Sample {}
+
+        static BUF: [Sample; 6] = [Sample(0); 6];
+
+        let (ptr, size_local) = api_read(&BUF);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<Sample>() });
+        assert_eq!(size_local, 6);
+    }
+
+    #[test]
+    fn read_api_transparent_word() {
+        transparent_word! {
+            #[derive(Clone, Copy)]
+            struct Sample(u16);
+        }
+
+        static BUF: [Sample; 4] = [Sample(0); 4];
+
+        let (ptr, size_local) = api_read(&BUF);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<Sample>() });
+        assert_eq!(size_local, 4);
+    }
+
+    #[test]
+    fn read_api_dma_word() {
+        dma_word! {
+            #[derive(Clone, Copy)]
+            struct Frame {
+                left: i16,
+                right: i16,
+            }
+        }
+
+        static BUF: [Frame; 4] = [Frame { left: 0, right: 0 }; 4];
+
+        let (ptr, size_local) = api_read(&BUF);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<Frame>() });
+        assert_eq!(size_local, 4);
+        assert_eq!(<&'static [Frame; 4] as ReadBuffer>::WORD_SIZE, 4);
+    }
+
+    #[test]
+    fn valid_word_size() {
+        assert!(is_valid_word_size::<u8>());
+        assert!(is_valid_word_size::<u32>());
+        assert!(is_valid_word_size::<[u16; 3]>());
+        assert!(!is_valid_word_size::<()>());
+        assert!(!is_valid_word_size::<[u32; 0]>());
+    }
+
+    #[test]
+    fn unsafe_cell_target() {
+        let mut cell = UnsafeCell::new([0u8; 16]);
+        let ptr = cell.get() as *mut u8;
+        assert_eq!(cell.as_read_buffer(), (ptr as *const u8, 16));
+        assert_eq!(cell.as_write_buffer(), (ptr, 16));
+    }
+
+    #[test]
+    fn cell_target() {
+        let mut cell = Cell::new([0u8; 16]);
+        let ptr = cell.as_ptr() as *mut u8;
+        assert_eq!(cell.as_read_buffer(), (ptr as *const u8, 16));
+        assert_eq!(cell.as_write_buffer(), (ptr, 16));
+
+        static mut BUF: Cell<[u16; 4]> = Cell::new([0; 4]);
+
+        let (ptr, size_local) = api_write(unsafe { &mut *addr_of_mut!(BUF) });
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u16>() });
+        assert_eq!(size_local, 4);
+    }
+
+    #[test]
+    fn tuple_targets() {
+        static PAIR: (u32, [u32; 4]) = (0, [0; 4]);
+        static mut TRIPLE: (u16, [u16; 2], u16) = (0, [0; 2], 0);
+
+        let (ptr, size_local) = api_read(&PAIR);
+        assert_eq!(ptr, &PAIR as *const _ as *const u32);
+        assert_eq!(size_local, 5);
+
+        let triple = unsafe { &mut *addr_of_mut!(TRIPLE) };
+        let base = triple as *mut _ as *mut u16;
+        let (ptr, size_local) = api_write(triple);
+        assert_eq!(ptr, base);
+        assert_eq!(size_local, 4);
+    }
+
+    #[test]
+    fn aligned() {
+        use alignment::*;
+
+        assert_eq!(mem::align_of::<Aligned<A4, [u8; 4]>>(), 4);
+        assert_eq!(mem::align_of::<Aligned<A8, [u8; 4]>>(), 8);
+        assert_eq!(mem::align_of::<Aligned<A16, [u8; 4]>>(), 16);
+        assert_eq!(mem::align_of::<Aligned<A32, [u8; 4]>>(), 32);
+        assert_eq!(mem::align_of::<Aligned<A64, [u8; 4]>>(), 64);
+
+        static BUF: Aligned<A32, [u8; 4]> = Aligned::new([0; 4]);
+        static mut RX: Aligned<A64, [u16; 8]> = Aligned::new([0; 8]);
+
+        let (ptr, size_local) = api_read(&BUF);
+        assert_eq!(ptr, BUF.as_ptr());
+        assert_eq!(ptr as usize % 32, 0);
+        assert_eq!(size_local, 4);
+
+        let (ptr, size_local) = api_write(unsafe { &mut *addr_of_mut!(RX) });
+        assert_eq!(ptr as usize % 64, 0);
+        assert_eq!(size_local, 8);
+    }
+
+    #[test]
+    fn target_alignment() {
+        #[allow(dead_code)]
+        #[repr(align(32))]
+        struct Aligned32([u8; 32]);
+
+        unsafe impl ReadTarget for Aligned32 {
+            type Word = u8;
+        }
+
+        unsafe impl WriteTarget for Aligned32 {
+            type Word = u8;
+        }
+
+        let array = [0u32; 4];
+        assert_eq!(ReadTarget::alignment(&array), 4);
+        assert_eq!(WriteTarget::alignment(&array), 4);
+
+        let aligned = Aligned32([0; 32]);
+        assert_eq!(ReadTarget::alignment(&aligned), 32);
+        assert_eq!(WriteTarget::alignment(&aligned), 32);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn boxed_slice() {
+        use std::{boxed::Box, vec};
+
+        for &len in &[0, 1, 1024] {
+            let mut buffer: Box<[u8]> = vec![0; len].into_boxed_slice();
+            let expected = (buffer.as_mut_ptr(), len);
+            assert_eq!(api_write(buffer), expected);
+        }
+
+        let (ptr, size_local) = api_read(Box::<[u32]>::from([]));
+        assert!(!ptr.is_null());
+        assert_eq!(ptr as usize % mem::align_of::<u32>(), 0);
+        assert_eq!(size_local, 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn shared_slices() {
+        use std::{rc::Rc, sync::Arc};
+
+        let arc: Arc<[u8]> = Arc::from(&[1, 2, 3][..]);
+        let expected = (arc.as_ptr(), 3);
+        assert_eq!(api_read(arc.clone()), expected);
+
+        let rc: Rc<[u16]> = Rc::from(&[1, 2][..]);
+        let expected = (rc.as_ptr(), 2);
+        assert_eq!(api_read(rc), expected);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn pinned_box() {
+        use core::pin::Pin;
+        use std::{boxed::Box, vec};
+
+        let mut pinned: Pin<Box<[u8]>> = Box::into_pin(vec![0; 16].into_boxed_slice());
+        let expected = (pinned.as_mut_ptr(), 16);
+        assert_eq!(api_write(Pin::into_inner(pinned)), expected);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reusable() {
+        use std::{boxed::Box, vec};
+
+        let buffer: Box<[u8]> = vec![0; 16].into_boxed_slice();
+        let mut reusable = Reusable::new(buffer);
+        let (ptr, len) = unsafe { reusable.write_buffer() };
+        unsafe { ptr::write_bytes(ptr, 0xaa, len) };
+
+        reusable.reset();
+        assert!(reusable.into_inner().iter().all(|&byte| byte == 0));
+    }
+
+    #[cfg(feature = "test-utils")]
+    fn simulate_rx<B: WriteBuffer<Word = u8>>(buffer: &mut B, data: &[u8]) {
+        let (ptr, len) = unsafe { buffer.write_buffer() };
+        let len = len.min(data.len());
+        unsafe { ptr.copy_from_nonoverlapping(data.as_ptr(), len) };
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn mock_buffer() {
+        let mut buffer = MockDmaBuffer::new(std::vec![0u8; 4]);
+        simulate_rx(&mut buffer, &[1, 2, 3, 4]);
+        assert!(buffer.in_use());
+        assert_eq!(buffer.write_calls(), 1);
+        assert_eq!(buffer.read_calls(), 0);
+
+        buffer.complete();
+        assert_eq!(buffer.contents(), &[1, 2, 3, 4]);
+        buffer.contents_mut()[0] = 5;
+
+        let (ptr, len) = unsafe { buffer.read_buffer() };
+        assert_eq!((ptr, len), (buffer.contents().as_ptr(), 4));
+        assert_eq!(buffer.read_calls(), 1);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    #[should_panic(expected = "DMA buffer modified while in use")]
+    fn mock_buffer_modified_in_use() {
+        let mut buffer = MockDmaBuffer::new(std::vec![0u8; 4]);
+        simulate_rx(&mut buffer, &[1, 2]);
+        buffer.contents_mut()[0] = 5;
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn read_buffer_invariants() {
+        static BUF: [u8; 16] = [0; 16];
+
+        assert_read_buffer_invariants(&&BUF[..]);
+    }
+
+    /// Buffer moving forward by one word on every call.
+    #[cfg(feature = "test-utils")]
+    struct Unstable(core::cell::Cell<usize>);
+
+    #[cfg(feature = "test-utils")]
+    unsafe impl ReadBuffer for Unstable {
+        type Word = u8;
+
+        unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+            static BUF: [u8; 16] = [0; 16];
+
+            let offset = self.0.replace(self.0.get() + 1);
+            (BUF.as_ptr().add(offset), 1)
+        }
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    #[should_panic(expected = "DMA buffer changed between calls to read_buffer")]
+    fn read_buffer_invariants_unstable() {
+        assert_read_buffer_invariants(&Unstable(core::cell::Cell::new(0)));
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn write_buffer_stable() {
+        static mut BUF: [u8; 16] = [0; 16];
+
+        let mut buffer = unsafe { &mut *addr_of_mut!(BUF) };
+        assert_write_buffer_stable!(buffer, 4);
+    }
+
+    #[cfg(feature = "test-utils")]
+    unsafe impl WriteBuffer for Unstable {
+        type Word = u8;
+
+        unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+            let len = self.0.replace(self.0.get() + 1);
+            (NonNull::dangling().as_ptr(), len)
+        }
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    #[should_panic(expected = "DMA buffer changed between calls to write_buffer")]
+    fn write_buffer_unstable() {
+        assert_write_buffer_stable!(Unstable(core::cell::Cell::new(0)), 2);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn poisoned_buffer() {
+        let mut buffer = MockDmaBuffer::new(std::vec![0u8; 16]);
+        let guard = poison_and_check(&mut buffer, 0xaa);
+        unsafe { guard.verify(0) };
+        simulate_rx(&mut buffer, &[0; 4]);
+        unsafe { guard.verify(4) };
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    #[should_panic(expected = "DMA buffer overrun at byte 4")]
+    fn poisoned_buffer_overrun() {
+        let mut buffer = MockDmaBuffer::new(std::vec![0u8; 16]);
+        let guard = poison_and_check(&mut buffer, 0xaa);
+        simulate_rx(&mut buffer, &[0; 8]);
+        unsafe { guard.verify(4) };
+    }
+
+    #[cfg(feature = "fixed")]
+    #[test]
+    fn read_api_fixed() {
+        use fixed::types::I16F16;
+
+        static BUF: [I16F16; 8] = [I16F16::ZERO; 8];
+
+        assert_eq!(<&[I16F16; 8] as ReadBuffer>::WORD_SIZE, 4);
+        let (ptr, size_local) = api_read(&BUF);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<I16F16>() });
+        assert_eq!(size_local, 8);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn write_api_pod_word() {
+        #[derive(Clone, Copy)]
+        #[repr(C)]
+        struct Registers {
+            status: u32,
+            data: u32,
+        }
+
+        unsafe impl bytemuck::Zeroable for Registers {}
+        unsafe impl bytemuck::Pod for Registers {}
+
+        static mut BUF: [PodWord<Registers>; 4] = [PodWord(Registers { status: 0, data: 0 }); 4];
+
+        let (ptr, size_local) = api_write(unsafe { &mut *addr_of_mut!(BUF) });
+        assert!(unsafe { (&*ptr as &dyn Any).is::<PodWord<Registers>>() });
+        assert_eq!(size_local, 4);
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn read_api_arrayvec() {
+        static mut VEC: arrayvec::ArrayVec<u8, 8> = arrayvec::ArrayVec::new_const();
+
+        let vec = unsafe { &mut *addr_of_mut!(VEC) };
+        vec.try_extend_from_slice(&[1, 2, 3]).unwrap();
+
+        let (ptr, size_local) = api_read(Growable::new(&*vec));
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u8>() });
+        assert_eq!(size_local, 3);
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn write_api_arrayvec() {
+        static mut VEC: arrayvec::ArrayVec<u16, 8> = arrayvec::ArrayVec::new_const();
+
+        let vec = unsafe { &mut *addr_of_mut!(VEC) };
+        vec.push(1);
+        let ptr = vec.as_mut_ptr();
+
+        let mut buffer = Growable::new(vec);
+        assert_eq!(unsafe { buffer.write_buffer_capacity() }, (ptr, 8));
+        let (ptr, size_local) = api_write(buffer);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u16>() });
+        assert_eq!(size_local, 1);
+    }
+
+    #[cfg(feature = "zerocopy")]
+    #[test]
+    fn zerocopy_word() {
+        #[derive(Clone, Copy, zerocopy::FromBytes, zerocopy::FromZeroes, zerocopy::AsBytes)]
+        #[repr(C)]
+        struct Frame {
+            header: u16,
+            payload: u16,
+        }
+
+        static FRAMES: [Frame; 3] = [Frame {
+            header: 0,
+            payload: 0,
+        }; 3];
+        static mut RX_FRAMES: [Frame; 5] = [Frame {
+            header: 0,
+            payload: 0,
+        }; 5];
+
+        let (ptr, size_local) = api_read(ZeroCopyWord::from_slice(&FRAMES));
+        assert_eq!(ptr as *const Frame, FRAMES.as_ptr());
+        assert_eq!(size_local, 3);
+
+        let rx_frames = unsafe { &mut *addr_of_mut!(RX_FRAMES) };
+        let (ptr, size_local) = api_write(ZeroCopyWord::from_mut_slice(rx_frames));
+        assert!(unsafe { (&*ptr as &dyn Any).is::<ZeroCopyWord<Frame>>() });
+        assert_eq!(size_local, 5);
+    }
+
+    #[cfg(feature = "generic-array")]
+    #[test]
+    fn generic_array_target() {
+        use generic_array::{typenum::U64, GenericArray};
+
+        let mut array = GenericArray::<u16, U64>::default();
+        let (ptr, len) = array.as_read_buffer();
+        assert_eq!(ptr, array.as_ptr());
+        assert_eq!(len, 64);
+
+        let (ptr, len) = array.as_write_buffer();
+        assert_eq!(ptr, array.as_mut_ptr());
+        assert_eq!(len, 64);
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes() {
+        let buffer = BytesBuffer::new(bytes::Bytes::from_static(&[1, 2, 3, 4]));
+        let expected = (buffer.buffer.as_ptr(), 4);
+        assert_eq!(unsafe { buffer.read_buffer() }, expected);
+        assert_eq!(api_read(buffer), expected);
+
+        let mut buffer = bytes::BytesMut::with_capacity(16);
+        buffer.extend_from_slice(&[0; 8]);
+        let mut buffer = BytesBuffer::new(buffer);
+        let ptr = buffer.buffer.as_mut_ptr();
+        let capacity = buffer.buffer.capacity();
+        assert_eq!(unsafe { buffer.read_buffer() }, (ptr as *const u8, 8));
+        assert_eq!(unsafe { buffer.write_buffer_capacity() }, (ptr, capacity));
+        assert_eq!(api_write(buffer), (ptr, 8));
+    }
+
+    #[cfg(feature = "cortex-m")]
+    #[test]
+    fn cache_line_rounding() {
+        assert_eq!(cache_line_range(0x2000_0004, 33), (0x2000_0000, 64));
+        assert_eq!(cache_line_range(0x2000_0020, 32), (0x2000_0020, 32));
+        assert_eq!(cache_line_range(0x2000_001f, 1), (0x2000_0000, 32));
+        assert_eq!(cache_line_range(0x2000_0004, 0), (0x2000_0004, 0));
+        assert_eq!(cache_line_range(usize::MAX - 3, 4), (usize::MAX - 31, 32));
+        assert_eq!(cache_line_range(usize::MAX - 3, 1), (usize::MAX - 31, 32));
+    }
+
+    #[test]
+    fn overlapping_buffers() {
+        let mut mem = [0u32; 8];
+        let base = mem.as_mut_ptr();
+        let words = |offset: usize, len| unsafe {
+            Fixed::new(NonNull::new_unchecked(base.add(offset)), len)
+        };
+        let bytes = |len| unsafe { Fixed::new(NonNull::new_unchecked(base as *mut u8), len) };
+
+        assert!(!buffers_overlap(&words(0, 4), &mut words(4, 4)));
+        assert!(buffers_overlap(&words(0, 4), &mut words(0, 4)));
+        assert!(buffers_overlap(&bytes(12), &mut words(2, 4)));
+        assert!(!buffers_overlap(&bytes(8), &mut words(2, 4)));
+        assert!(!buffers_overlap(&words(0, 0), &mut words(0, 4)));
+    }
+
+    #[test]
+    fn repeat() {
+        static FILL: Repeat<u16> = unsafe { Repeat::new(0xf800, 320 * 240) };
+
+        let (ptr, size_local) = api_read(&FILL);
+        assert_eq!(unsafe { *ptr }, 0xf800);
+        assert_eq!(size_local, 320 * 240);
+        assert_eq!(api_read(&FILL), (ptr, size_local));
+    }
+
+    #[test]
+    fn flash_buffer() {
+        fn flash_transfer<B: FlashBuffer>(buffer: B) -> (*const B::Word, usize) {
+            unsafe { buffer.read_buffer() }
+        }
+
+        let (ptr, size_local) = flash_transfer(flash!([u8; 16] = [0x55; 16]));
+        assert_eq!(unsafe { *ptr }, 0x55);
+        assert_eq!(size_local, 16);
+    }
+
+    #[test]
+    fn duplex_pair() {
+        static TX: [u8; 8] = [0; 8];
+        static mut RX: [u8; 8] = [0; 8];
+        static mut SHORT: [u8; 4] = [0; 4];
+
+        let rx = unsafe { &mut *addr_of_mut!(RX) };
+        let expected = (rx.as_mut_ptr(), 8);
+        let mut pair = DuplexPair::new(&TX, rx).unwrap();
+        assert_eq!(unsafe { pair.tx().read_buffer() }, (TX.as_ptr(), 8));
+        assert_eq!(unsafe { pair.rx().write_buffer() }, expected);
+
+        let short = unsafe { &mut *addr_of_mut!(SHORT) };
+        assert_eq!(
+            DuplexPair::new(&TX, short).err(),
+            Some(LengthMismatch { tx: 8, rx: 4 })
+        );
+    }
+
+    #[test]
+    fn scatter_list() {
+        static HEADER: [u8; 4] = [0; 4];
+        static PAYLOAD: [u8; 64] = [0; 64];
+        static CRC: u32 = 0;
+
+        let mut list = ScatterList::<3>::new();
+        list.push(&&HEADER).unwrap();
+        list.push(&&PAYLOAD).unwrap();
+        list.push(&&CRC).unwrap();
+        assert_eq!(list.push(&&HEADER), Err(FullError));
+
+        let expected = [
+            Descriptor {
+                address: HEADER.as_ptr() as usize,
+                len: 4,
+            },
+            Descriptor {
+                address: PAYLOAD.as_ptr() as usize,
+                len: 64,
+            },
+            Descriptor {
+                address: &CRC as *const u32 as usize,
+                len: 1,
+            },
+        ];
+        assert_eq!(list.descriptors(), &expected[..]);
+    }
+
+    /// Target of four bytes, claiming `u32` words without being aligned for them.
+    #[allow(dead_code)]
+    struct Misaligned([u8; 4]);
+
+    unsafe impl ReadTarget for Misaligned {
+        type Word = u32;
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        should_panic(expected = "DMA buffer is not aligned for its word type")
+    )]
+    fn misaligned_target() {
+        #[repr(C, align(4))]
+        struct Holder {
+            _pad: u8,
+            target: Misaligned,
+        }
+
+        let holder = Holder {
+            _pad: 0,
+            target: Misaligned([0; 4]),
+        };
+        let (_, len) = holder.target.as_read_buffer();
+        assert_eq!(len, 1);
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        should_panic(expected = "DMA buffer is not aligned for its word type")
+    )]
+    fn misaligned_unsafe_cell() {
+        #[repr(C, align(4))]
+        struct Holder {
+            _pad: u8,
+            target: UnsafeCell<Misaligned>,
+        }
+
+        let holder = Holder {
+            _pad: 0,
+            target: UnsafeCell::new(Misaligned([0; 4])),
+        };
+        let (_, len) = holder.target.as_read_buffer();
+        assert_eq!(len, 1);
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        should_panic(expected = "DMA buffer is not aligned for its word type")
+    )]
+    fn misaligned_cell() {
+        #[repr(C, align(4))]
+        struct Holder {
+            _pad: u8,
+            target: Cell<Misaligned>,
+        }
+
+        let holder = Holder {
+            _pad: 0,
+            target: Cell::new(Misaligned([0; 4])),
+        };
+        let (_, len) = holder.target.as_read_buffer();
+        assert_eq!(len, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_sized_cell() {
+        #[derive(Clone, Copy)]
+        struct Nothing;
+
+        unsafe impl Word for Nothing {}
+
+        let mut cell = Cell::new([Nothing; 4]);
+        cell.as_write_buffer();
+    }
+
+    #[test]
+    fn nested_arrays() {
+        static mut LINES: [[u8; 16]; 8] = [[0; 16]; 8];
+
+        let lines = unsafe { &mut *addr_of_mut!(LINES) };
+        let base = lines.as_mut_ptr() as *mut u8;
+        let (ptr, size_local) = api_write(lines);
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u8>() });
+        assert_eq!(ptr, base);
+        assert_eq!(size_local, 128);
+    }
+
+    #[test]
+    fn auto_aligned() {
+        static BUF: Aligned<alignment::A16, [u8; 64]> = Aligned::new([0; 64]);
+
+        let unaligned = Sub::new(&BUF, 3, 61).unwrap();
+        let aligned = AutoAligned::new(unaligned, 16).unwrap();
+        let (ptr, size_local) = api_read(aligned);
+        assert_eq!(ptr, BUF[16..].as_ptr());
+        assert_eq!(ptr as usize % 16, 0);
+        assert_eq!(size_local, 48);
+
+        let short = Sub::new(&BUF, 3, 8).unwrap();
+        assert!(AutoAligned::new(short, 16).is_none());
+        assert!(AutoAligned::new(&BUF, 3).is_none());
+    }
+
+    #[test]
+    fn truncated() {
+        static BUF: [u8; 512] = [0; 512];
+
+        let truncated = Truncated::new(&BUF, 100).unwrap();
+        assert_eq!(api_read(truncated), (BUF.as_ptr(), 100));
+
+        assert!(Truncated::new(&BUF, 600).is_none());
+    }
+
+    #[test]
+    fn burst_padded() {
+        static BUF: [u32; 17] = [0; 17];
+
+        let padded = BurstPadded::new(&BUF, 4).unwrap();
+        assert_eq!(unsafe { padded.remainder() }, (&BUF[16] as *const u32, 1));
+        assert_eq!(api_read(padded), (BUF.as_ptr(), 16));
+
+        assert!(BurstPadded::new(&BUF, 0).is_none());
+    }
+
+    #[test]
+    fn opaque_buffer() {
+        static mut BUF: [u8; 16] = [0; 16];
+
+        let ptr = unsafe { &mut *addr_of_mut!(BUF) }.as_mut_ptr() as *mut core::ffi::c_void;
+        let mut buffer: OpaqueBuffer = unsafe { Fixed::from_raw(ptr as *mut u8, 16) };
+        assert_eq!(unsafe { buffer.read_buffer() }, (ptr as *const u8, 16));
+        assert_eq!(unsafe { buffer.write_buffer() }, (ptr as *mut u8, 16));
+    }
+
+    #[test]
+    fn framed() {
+        static FRAME: [u16; 10] = [5, 1, 2, 3, 4, 5, 0, 0, 0, 0];
+        static OVERSIZED: [u16; 10] = [10, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+        let framed = Framed::new(&FRAME);
+        assert_eq!(
+            unsafe { framed.payload() },
+            Some((&FRAME[1] as *const u16, 5))
+        );
+        assert_eq!(api_read(framed), (FRAME.as_ptr(), 10));
+
+        assert_eq!(unsafe { Framed::new(&OVERSIZED).payload() }, None);
+    }
+
+    #[test]
+    fn buffer_is_empty() {
+        static EMPTY: [u8; 0] = [];
+        static mut ONE: [u16; 1] = [0];
+
+        unsafe {
+            assert!((&EMPTY[..]).read_buffer_is_empty());
+            assert!(!(&mut *addr_of_mut!(ONE)).write_buffer_is_empty());
+        }
+    }
+
+    #[test]
+    fn reinterpret_words() {
+        static mut BUF: Aligned<alignment::A4, [u8; 64]> = Aligned::new([0; 64]);
+        static ODD: [u8; 5] = [0; 5];
+
+        let buf = unsafe { &mut *addr_of_mut!(BUF) };
+        let expected = (buf.as_mut_ptr() as *mut u32, 16);
+        let words = ReinterpretWords::<_, u32>::new_mut(buf).unwrap();
+        assert_eq!(api_write(words), expected);
+
+        assert!(ReinterpretWords::<_, u32>::new(&ODD).is_none());
+    }
+
+    /// Logger for host tests, discarding all messages unless they are captured with
+    /// [`defmt_capture`].
+    #[cfg(feature = "defmt")]
+    #[defmt::global_logger]
+    struct Logger;
+
+    #[cfg(feature = "defmt")]
+    defmt::timestamp!("{=u32}", 0);
+
+    #[cfg(feature = "defmt")]
+    std::thread_local! {
+        static DEFMT_CAPTURE: core::cell::RefCell<Option<std::vec::Vec<u8>>> =
+            core::cell::RefCell::new(None);
+    }
+
+    #[cfg(feature = "defmt")]
+    unsafe impl defmt::Logger for Logger {
+        fn acquire() {}
+        unsafe fn flush() {}
+        unsafe fn release() {}
+        unsafe fn write(bytes: &[u8]) {
+            DEFMT_CAPTURE.with(|capture| {
+                if let Some(encoded) = capture.borrow_mut().as_mut() {
+                    encoded.extend_from_slice(bytes);
+                }
+            });
+        }
+    }
+
+    /// Run `log`, returning the encoded `defmt` frames it logged on the current thread.
+    #[cfg(feature = "defmt")]
+    fn defmt_capture(log: impl FnOnce()) -> std::vec::Vec<u8> {
+        DEFMT_CAPTURE.with(|capture| *capture.borrow_mut() = Some(std::vec::Vec::new()));
+        log();
+        DEFMT_CAPTURE.with(|capture| capture.borrow_mut().take().unwrap())
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn logged() {
+        static mut BUF: [u16; 8] = [0; 8];
+
+        let buf = unsafe { &mut *addr_of_mut!(BUF) };
+        let expected = (buf.as_mut_ptr(), 8);
+        let logged = Logged::new(buf);
+        assert_eq!(
+            unsafe { logged.read_buffer() },
+            (expected.0 as *const u16, 8)
+        );
+        assert_eq!(api_write(logged), expected);
+    }
+
+    // The interned format strings can only be decoded with the ELF file, but the arguments are
+    // written in order, with `usize` values encoded as little endian `u32`s. Messages are logged at
+    // error level, as lower levels are filtered out unless enabled with `DEFMT_LOG`.
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn defmt_format() {
+        fn assert_contains(encoded: &[u8], args: &[u8]) {
+            assert!(
+                encoded.windows(args.len()).any(|window| window == args),
+                "{:02x?} doesn't contain {:02x?}",
+                encoded,
+                args
+            );
+        }
+
+        fn args(values: &[usize]) -> std::vec::Vec<u8> {
+            values
+                .iter()
+                .flat_map(|&value| (value as u32).to_le_bytes())
+                .collect()
+        }
+
+        static BUF: [u32; 4] = [0; 4];
+
+        let info = unsafe { (&BUF).read_buffer_info() };
+        let encoded = defmt_capture(|| defmt::error!("{}", info));
+        assert_contains(&encoded, &args(&[BUF.as_ptr() as usize, 4, 4, 16]));
+
+        let unaligned = UnalignedError {
+            ptr: 0x2000_0002,
+            required: 4,
+        };
+        let encoded = defmt_capture(|| defmt::error!("{}", unaligned));
+        assert_contains(&encoded, &args(&[0x2000_0002, 4]));
+
+        let out_of_region = OutOfRegionError {
+            addr: 0x1000_0000,
+            bytes: 16,
+        };
+        let encoded = defmt_capture(|| defmt::error!("{}", out_of_region));
+        assert_contains(&encoded, &args(&[0x1000_0000, 16]));
+
+        let too_large = TooLargeError {
+            words: 70000,
+            max: 65535,
+        };
+        let encoded = defmt_capture(|| defmt::error!("{}", too_large));
+        assert_contains(&encoded, &args(&[70000, 65535]));
+
+        let too_short = TooShortError { words: 2, min: 4 };
+        let encoded = defmt_capture(|| defmt::error!("{}", too_short));
+        assert_contains(&encoded, &args(&[2, 4]));
+    }
+
+    #[test]
+    fn directed_buffer() {
+        static mut BUF: [u8; 16] = [0; 16];
+
+        let buf = unsafe { &mut *addr_of_mut!(BUF) };
+        let expected = (buf.as_mut_ptr(), 16);
+        let tx = DirectedBuffer::read(buf);
+        assert_eq!(unsafe { tx.read_buffer() }, (expected.0 as *const u8, 16));
+
+        let mut rx = tx.into_write();
+        assert_eq!(unsafe { rx.write_buffer() }, expected);
+    }
+
+    #[test]
+    fn static_guard_release() {
+        static mut BUF: [u8; 4] = [0; 4];
+
+        let buf = unsafe { &mut *addr_of_mut!(BUF) };
+        let expected = (buf.as_mut_ptr(), 4);
+        let mut guard = StaticGuard::new(buf);
+        assert_eq!(unsafe { guard.write_buffer() }, expected);
+        assert_eq!(api_write(guard.release()), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "DMA buffer dropped before being released")]
+    fn static_guard_early_drop() {
+        let buf = [0u16; 4];
+        let guard = StaticGuard::new(&buf);
+        drop(guard);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn ndarray() {
+        use ndarray::{s, Array1};
+        use std::vec;
+
+        let mut array = Array1::from(vec![0.0f32; 8]);
+        let expected = (array.as_mut_ptr(), 8);
+        let buffer = ContiguousArray::new(array).unwrap();
+        assert_eq!(api_write(buffer), expected);
+
+        let strided = Array1::from(vec![0.0f32; 8]).slice_move(s![..;2]);
+        assert!(ContiguousArray::new(strided).is_none());
+    }
+
+    #[cfg(feature = "spin")]
+    #[test]
+    fn spin_locked() {
+        static BUF: spin::Mutex<[u8; 32]> = spin::Mutex::new([0; 32]);
+
+        let mut locked = Locked::new(BUF.lock());
+        let expected = (locked.guard.as_mut_ptr(), 32);
+        assert_eq!(unsafe { locked.write_buffer() }, expected);
+        assert!(BUF.try_lock().is_none());
+
+        drop(locked.into_inner());
+        assert!(BUF.try_lock().is_some());
+    }
+
+    #[cfg(feature = "spin")]
+    #[test]
+    fn spin_locked_capacity() {
+        struct Spare([u8; 8]);
+
+        unsafe impl WriteTarget for Spare {
+            type Word = u8;
+
+            fn as_write_buffer(&mut self) -> (*mut Self::Word, usize) {
+                (self.0.as_mut_ptr(), 4)
+            }
+
+            fn as_write_buffer_capacity(&mut self) -> (*mut Self::Word, usize) {
+                (self.0.as_mut_ptr(), 8)
+            }
+        }
+
+        static SPARE: spin::Mutex<Spare> = spin::Mutex::new(Spare([0; 8]));
+
+        let mut locked = Locked::new(SPARE.lock());
+        let ptr = locked.guard.0.as_mut_ptr();
+        assert_eq!(unsafe { locked.write_buffer() }, (ptr, 4));
+        assert_eq!(unsafe { locked.write_buffer_capacity() }, (ptr, 8));
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn smallvec() {
+        use smallvec::SmallVec;
+        use std::boxed::Box;
+
+        let inline: &'static _ = Box::leak(Box::new(SmallVec::<[u8; 4]>::from_slice(&[1, 2])));
+        assert!(!inline.spilled());
+        assert_eq!(api_read(Growable::new(inline)), (inline.as_ptr(), 2));
+
+        let spilled = Box::leak(Box::new(SmallVec::<[u16; 4]>::from_slice(&[0; 8])));
+        assert!(spilled.spilled());
+        let expected = (spilled.as_mut_ptr(), 8);
+        assert_eq!(api_write(Growable::new(spilled)), expected);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn heapless_pool_box() {
+        use heapless::pool::singleton::Pool;
+
+        heapless::pool!(P: [u16; 32]);
+        static mut MEMORY: [u8; 256] = [0; 256];
+
+        P::grow(unsafe { &mut *addr_of_mut!(MEMORY) });
+        let mut buffer = P::alloc().unwrap().init([0; 32]);
+        let expected = (buffer.as_mut_ptr(), 32);
+        assert_eq!(api_write(buffer), expected);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn read_api_heapless_vec() {
+        static mut VEC: heapless::Vec<u8, 8> = heapless::Vec::new();
+
+        let vec = unsafe { &mut *addr_of_mut!(VEC) };
+        vec.extend_from_slice(&[1, 2, 3]).unwrap();
+
+        let (ptr, size_local) = api_read(Growable::new(&*vec));
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u8>() });
+        assert_eq!(size_local, 3);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn write_api_heapless_vec() {
+        static mut VEC: heapless::Vec<u16, 8> = heapless::Vec::new();
+
+        let vec = unsafe { &mut *addr_of_mut!(VEC) };
+        vec.push(1).unwrap();
+
+        let (ptr, size_local) = api_write(Growable::new(vec));
+        assert!(unsafe { (&*ptr as &dyn Any).is::<u16>() });
+        assert_eq!(size_local, 1);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn write_buffer_capacity_heapless_vec() {
+        static mut VEC: heapless::Vec<u8, 8> = heapless::Vec::new();
+
+        let vec = unsafe { &mut *addr_of_mut!(VEC) };
+        vec.extend_from_slice(&[1, 2, 3, 4]).unwrap();
+        let ptr = vec.as_mut_ptr();
+
+        let mut buffer = Growable::new(vec);
+        assert_eq!(unsafe { buffer.write_buffer() }, (ptr, 4));
+        assert_eq!(unsafe { buffer.write_buffer_capacity() }, (ptr, 8));
+    }
+
+    #[cfg(all(feature = "heapless", feature = "alloc"))]
+    #[test]
+    fn boxed_heapless_vec() {
+        use std::boxed::Box;
+
+        let mut vec = Box::new(heapless::Vec::<u32, 4>::new());
+        vec.push(1).unwrap();
+        let ptr = vec.as_mut_ptr();
+
+        let mut buffer = Growable::new(vec);
+        assert_eq!(unsafe { buffer.read_buffer() }, (ptr as *const u32, 1));
+        assert_eq!(unsafe { buffer.write_buffer_capacity() }, (ptr, 4));
     }
 }